@@ -0,0 +1,31 @@
+use std::time::Instant;
+use shanks_core::board::{Board, Color};
+use shanks_engine::{play_match, Engine, EngineOptions, MatchConfig, Player, PositionalEvaluator, SearchResult, Skill};
+struct Log<'a>(&'a mut Engine, &'static str);
+impl Player for Log<'_> {
+    fn name(&self) -> String { String::new() }
+    fn think(&mut self, board: &Board) -> SearchResult {
+        eprintln!("{} {}", self.1, board.fen());
+        let t = Instant::now();
+        let r = self.0.think(board);
+        eprintln!("  {:.1}s {}", t.elapsed().as_secs_f64(), r.nodes);
+        r
+    }
+    fn new_game(&mut self) { eprintln!("new game {}", self.1); Player::new_game(self.0) }
+}
+fn main() {
+    let options = EngineOptions { tt_size: 1 << 18, ..Default::default() };
+    let mut player = Engine::with_evaluator(Color::White, EngineOptions { depth: 12, ..options }, Box::new(PositionalEvaluator::default()));
+    player.seed(0);
+    let mut reference = Engine::with_evaluator(Color::White, EngineOptions { depth: 6, ..options }, Box::new(PositionalEvaluator::default()));
+    let config = MatchConfig { ballots: 20, first_ballot: 1, max_plies: 200, clock: None };
+    for level in 19..=20 {
+    player.set_skill(Some(Skill::new(level)));
+    let mut last = Instant::now();
+    let stats = play_match(&mut Log(&mut player, "p"), &mut Log(&mut reference, "r"), &config, |game| {
+        println!("  ballot {} {:?} {} plies {:.1}s", game.ballot.number(), game.first_color, game.plies, last.elapsed().as_secs_f64());
+        last = Instant::now();
+    });
+    println!("level {}: +{} ={} -{}", level, stats.wins, stats.draws, stats.losses);
+    }
+}
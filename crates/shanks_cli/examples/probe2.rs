@@ -0,0 +1,15 @@
+use std::time::Instant;
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions, PositionalEvaluator, Skill};
+fn main() {
+    let fen = std::env::args().nth(1).unwrap();
+    let level: u8 = std::env::args().nth(2).unwrap().parse().unwrap();
+    let board = Board::from_fen(&fen).unwrap();
+    let mut e = Engine::with_evaluator(Color::White, EngineOptions { depth: 12, tt_size: 1 << 18, ..Default::default() }, Box::new(PositionalEvaluator::default()));
+    e.seed(0);
+    e.set_skill(Some(Skill::new(level)));
+    e.set_info_callback(Some(Box::new(|info| eprintln!("    line {} depth {} nodes {} seldepth {} score {}", info.line, info.depth, info.stats.nodes, info.stats.seldepth, info.score))));
+    let t = Instant::now();
+    let r = e.think(&board);
+    println!("{:?} {} {:.1}s", r.best_ply().map(|p| p.notation()), r.nodes, t.elapsed().as_secs_f64());
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use shanks_core::board::Board;
+use shanks_engine::{Engine, Tablebase};
+
+use crate::cli::AnalyzeArgs;
+
+pub fn analyze(args: AnalyzeArgs) {
+    let board = match Board::from_fen(&args.fen) {
+        Ok(board) => board,
+        Err(err) => {
+            println!("Invalid FEN: {}", err);
+            return;
+        }
+    };
+    let (evaluator, network) = match (args.evaluation.evaluator(), args.evaluation.network()) {
+        (Ok(evaluator), Ok(network)) => (evaluator, network),
+        (Err(err), _) | (_, Err(err)) => {
+            println!("Failed to load the evaluation: {}", err);
+            return;
+        }
+    };
+    let tablebase = match args.tablebase.as_ref().map(Tablebase::load).transpose() {
+        Ok(tablebase) => tablebase,
+        Err(err) => {
+            println!("Failed to load the tablebase: {}", err);
+            return;
+        }
+    };
+    let mut engine = Engine::with_evaluator(board.to_move(), args.engine_options(), evaluator);
+    engine.set_network(network);
+    engine.set_tablebase(tablebase.map(Arc::new));
+    if args.verbose {
+        engine.set_info_callback(Some(Box::new(|info| println!("{}", info))));
+    }
+
+    println!("{}", board);
+    println!("{} to move", board.to_move());
+    let lines = engine.analyze(&board);
+    if lines.is_empty() {
+        println!("No legal plies, {} has lost", board.to_move());
+        return;
+    }
+    for (rank, line) in lines.iter().enumerate() {
+        println!("{:>3}. {:>9.3}  depth {:>3}  {}", rank + 1, line.score, line.depth, line.pv_notation());
+    }
+    let stats = engine.stats();
+    println!("Nodes: {} ({:.0} nps) in {:.3}s", stats.nodes, stats.nps(), stats.elapsed.as_secs_f64());
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, Instant};
+
+use shanks_core::board::Board;
+use shanks_engine::{Engine, EngineOptions};
+
+use crate::cli::BenchArgs;
+
+/// Openings the benchmark is run on, given as indices into the legal plies of each position.
+const OPENINGS: [&[usize]; 4] = [&[], &[2, 2], &[3, 6, 1], &[6, 0, 4, 1]];
+
+pub fn bench(args: BenchArgs) {
+    let boards: Vec<Board> = OPENINGS.iter().map(|opening| play_opening(opening)).collect();
+
+    println!("Time to depth {} over {} positions", args.depth, boards.len());
+    let mut baseline = None;
+    let mut threads = 1;
+    while threads <= args.threads {
+        let options = EngineOptions { depth: args.depth, threads, ..Default::default() };
+        let mut elapsed = Duration::ZERO;
+        let mut nodes = 0;
+        for board in &boards {
+            let mut engine = Engine::with_options(board.to_move(), options);
+            let start = Instant::now();
+            nodes += engine.think(board).nodes;
+            elapsed += start.elapsed();
+        }
+
+        let baseline = *baseline.get_or_insert(elapsed);
+        println!(
+            "threads {:>3}: {:>8.3}s {:>12} nodes {:>10.0} nps  speedup {:.2}",
+            threads,
+            elapsed.as_secs_f64(),
+            nodes,
+            nodes as f64 / elapsed.as_secs_f64(),
+            baseline.as_secs_f64() / elapsed.as_secs_f64()
+        );
+        threads *= 2;
+    }
+}
+
+fn play_opening(opening: &[usize]) -> Board {
+    let mut board = Board::default();
+    for &index in opening {
+        if let Some(ply) = board.get_ply(index) {
+            board.ply(ply);
+        }
+    }
+    board
+}
@@ -0,0 +1,57 @@
+use std::fs;
+
+use shanks_core::board::{parse_pdn, Board, Color};
+use shanks_engine::{Book, Engine, EngineOptions};
+
+use crate::cli::BookArgs;
+
+pub fn book(args: BookArgs) {
+    let mut book = Book::new();
+    let mut games = 0;
+    for path in &args.games {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                println!("Failed to read {}: {}", path.display(), err);
+                return;
+            }
+        };
+        let parsed = match parse_pdn(&text) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                println!("Failed to parse {}: {}", path.display(), err);
+                return;
+            }
+        };
+        for (index, game) in parsed.iter().enumerate() {
+            match book.add_game(game, args.plies) {
+                Ok(()) => games += 1,
+                Err(err) => println!("Skipping game {} of {}: {}", index + 1, path.display(), err),
+            }
+        }
+    }
+    if games > 0 {
+        println!("Added {} games, {} positions", games, book.len());
+    }
+
+    if let Some(plies) = args.analyze {
+        let (evaluator, network) = match (args.evaluation.evaluator(), args.evaluation.network()) {
+            (Ok(evaluator), Ok(network)) => (evaluator, network),
+            (Err(err), _) | (_, Err(err)) => {
+                println!("Failed to load the evaluation: {}", err);
+                return;
+            }
+        };
+        let options = EngineOptions { depth: args.depth, ..Default::default() };
+        let mut engine = Engine::with_evaluator(Color::White, options, evaluator);
+        engine.set_network(network);
+        println!("Analyzing all positions within {} plies at depth {}...", plies, args.depth);
+        book.add_analysis(&mut engine, &Board::default(), plies);
+        println!("The book has {} positions", book.len());
+    }
+
+    match book.save(&args.output) {
+        Ok(()) => println!("Saved the book to {}", args.output.display()),
+        Err(err) => println!("Failed to save the book: {}", err),
+    }
+}
@@ -0,0 +1,45 @@
+use std::{io, time::Instant};
+
+use shanks_core::board::Color;
+use shanks_engine::{Engine, EngineOptions, MatchConfig, REFERENCE_ELO};
+
+use crate::cli::CalibrateArgs;
+
+pub fn calibrate(args: CalibrateArgs) {
+    let engine = |depth| -> io::Result<Engine> {
+        let options = EngineOptions { depth, tt_size: 1 << 18, ..Default::default() };
+        let mut engine = Engine::with_evaluator(Color::White, options, args.evaluation.evaluator()?);
+        engine.set_network(args.evaluation.network()?);
+        Ok(engine)
+    };
+    let (mut player, mut reference) = match (engine(args.depth), engine(args.reference_depth)) {
+        (Ok(player), Ok(reference)) => (player, reference),
+        (Err(err), _) | (_, Err(err)) => {
+            println!("Failed to load the evaluation: {}", err);
+            return;
+        }
+    };
+    player.seed(args.seed);
+    let config = MatchConfig { ballots: args.ballots, first_ballot: 1, max_plies: args.max_plies, clock: None };
+
+    println!(
+        "Playing {} games per level against depth {}, rated {}",
+        2 * args.ballots,
+        args.reference_depth,
+        REFERENCE_ELO
+    );
+    let start = Instant::now();
+    let ratings = shanks_engine::calibrate(&mut player, &mut reference, &config, |skill, stats, elo| {
+        println!(
+            "level {:>2}: +{:<3} ={:<3} -{:<3} {:>6.0} Elo {:>8.1}s",
+            skill,
+            stats.wins,
+            stats.draws,
+            stats.losses,
+            elo,
+            start.elapsed().as_secs_f64()
+        );
+    });
+    let ratings: Vec<String> = ratings.iter().map(|elo| format!("{:.1}", elo.round())).collect();
+    println!("[{}]", ratings.join(", "));
+}
@@ -1,4 +1,10 @@
-use clap::{Parser, Subcommand};
+use std::{io, path::PathBuf, time::Duration};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use shanks_engine::{
+    DatagenConfig, EngineOptions, EvalParams, Evaluator, MatchConfig, MaterialEvaluator, Network, Playout,
+    PositionalEvaluator, SelfPlayConfig, Skill, SolverOptions, TimeControl, ZeroOptions, ZeroTrainer,
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "shanks", version, about, author, long_about = None)]
@@ -11,12 +17,522 @@ pub struct Cli {
 pub enum SubCommand {
     Play(PlayArgs),
     Debug(DebugArgs),
+    Bench(BenchArgs),
+    Eval(EvalArgs),
+    Tune(TuneArgs),
+    Datagen(DatagenArgs),
+    Tbgen(TbgenArgs),
+    Tbverify(TbverifyArgs),
+    Book(BookArgs),
+    Match(MatchArgs),
+    Analyze(AnalyzeArgs),
+    Calibrate(CalibrateArgs),
+    Testsuite(TestsuiteArgs),
+    Zero(ZeroArgs),
+    Solve(SolveArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "play", about = "Play a game of checkers")]
-pub struct PlayArgs {}
+pub struct PlayArgs {
+    /// Maximum search depth of the engine
+    #[arg(short, long, default_value_t = shanks_engine::DEFAULT_DEPTH)]
+    pub depth: usize,
+    /// Disable principal variation search
+    #[arg(long)]
+    pub no_pvs: bool,
+    /// Disable aspiration windows
+    #[arg(long)]
+    pub no_aspiration: bool,
+    /// Disable late move reductions
+    #[arg(long)]
+    pub no_lmr: bool,
+    /// Disable the extensions of single replies and captures
+    #[arg(long)]
+    pub no_extensions: bool,
+    /// Number of search threads
+    #[arg(short, long, default_value_t = 1)]
+    pub threads: usize,
+    /// Directory with endgame tablebase files generated by "shanks tbgen"
+    #[arg(long)]
+    pub tablebase: Option<PathBuf>,
+    /// Opening book built by "shanks book"
+    #[arg(long)]
+    pub book: Option<PathBuf>,
+    /// Print the progress of every search iteration
+    #[arg(short, long)]
+    pub verbose: bool,
+    /// Search the position after the suggested ply while waiting for the next ply
+    #[arg(long)]
+    pub ponder: bool,
+    /// Weaken the engine to a skill level from 1 to 20
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=20))]
+    pub skill: Option<u8>,
+    /// Weaken the engine to about this rating, see "shanks calibrate"
+    #[arg(long, conflicts_with = "skill")]
+    pub elo: Option<f64>,
+    /// Think at most this many milliseconds per move
+    #[arg(long)]
+    pub movetime: Option<u64>,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+impl PlayArgs {
+    pub fn skill(&self) -> Option<Skill> {
+        self.skill.map(Skill::new).or_else(|| self.elo.map(Skill::from_elo))
+    }
+
+    pub fn engine_options(&self) -> EngineOptions {
+        EngineOptions {
+            depth: self.depth,
+            pvs: !self.no_pvs,
+            aspiration: !self.no_aspiration,
+            lmr: !self.no_lmr,
+            extensions: !self.no_extensions,
+            threads: self.threads,
+            movetime: self.movetime.map(Duration::from_millis),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalKind {
+    /// Material only
+    Material,
+    /// Material and positional checkers terms
+    Positional,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerKind {
+    /// The alpha-beta engine
+    AlphaBeta,
+    /// Monte Carlo tree search
+    Mcts,
+    /// Uniformly random plies
+    Random,
+    /// The ply with the best material balance, without looking ahead
+    Greedy,
+    /// Plain fixed-depth minimax without pruning
+    Minimax,
+    /// Tree search guided by a policy/value network trained with "shanks zero"
+    Zero,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayoutKind {
+    /// Random games until the end
+    Random,
+    /// The static evaluation after resolving captures
+    Evaluation,
+}
+
+impl From<PlayoutKind> for Playout {
+    fn from(kind: PlayoutKind) -> Self {
+        match kind {
+            PlayoutKind::Random => Playout::Random,
+            PlayoutKind::Evaluation => Playout::Evaluation,
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct EvalSelection {
+    /// Static evaluation used by the engine
+    #[arg(short, long, value_enum, default_value_t = EvalKind::Positional)]
+    pub eval: EvalKind,
+    /// File with tuned weights for the positional evaluation
+    #[arg(long)]
+    pub params: Option<PathBuf>,
+    /// File with NNUE weights, replacing the static evaluation when given
+    #[arg(long)]
+    pub nnue: Option<PathBuf>,
+}
+
+impl EvalSelection {
+    pub fn eval_params(&self) -> io::Result<EvalParams> {
+        match &self.params {
+            Some(path) => EvalParams::load(path),
+            None => Ok(EvalParams::default()),
+        }
+    }
+
+    pub fn network(&self) -> io::Result<Option<Network>> {
+        self.nnue.as_ref().map(Network::load).transpose()
+    }
+
+    pub fn evaluator(&self) -> io::Result<Box<dyn Evaluator>> {
+        Ok(match self.eval {
+            EvalKind::Material => Box::new(MaterialEvaluator),
+            EvalKind::Positional => Box::new(PositionalEvaluator::new(self.eval_params()?)),
+        })
+    }
+}
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "debug", about = "Debug a game of checkers")]
 pub struct DebugArgs {}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "bench", about = "Measure the time to depth of the engine for increasing thread counts")]
+pub struct BenchArgs {
+    /// Depth every position is searched to
+    #[arg(short, long, default_value_t = 14)]
+    pub depth: usize,
+    /// Maximum number of threads, the benchmark doubles the thread count up to it
+    #[arg(short, long, default_value_t = std::thread::available_parallelism().map_or(1, |n| n.get()))]
+    pub threads: usize,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "eval", about = "Explain the static evaluation of a position term by term")]
+pub struct EvalArgs {
+    /// The position in FEN notation, e.g. "W:W21-32:B1-12"
+    pub fen: String,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "tune", about = "Tune the positional evaluation on positions labelled with game results")]
+pub struct TuneArgs {
+    /// File with one "FEN;result" or "FEN;score;result" line per position, results are from white's point of view
+    pub positions: PathBuf,
+    /// File the tuned weights are written to
+    #[arg(short, long, default_value = "params.txt")]
+    pub output: PathBuf,
+    /// Weights to start from, the defaults are used if omitted
+    #[arg(long)]
+    pub params: Option<PathBuf>,
+    /// Number of epochs of gradient descent
+    #[arg(long, default_value_t = 1000)]
+    pub epochs: usize,
+    /// Step size of the gradient descent
+    #[arg(long, default_value_t = 0.001)]
+    pub learning_rate: f64,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "datagen", about = "Generate training positions from engine self-play games")]
+pub struct DatagenArgs {
+    /// Number of games to play
+    #[arg(short, long, default_value_t = 1000)]
+    pub games: usize,
+    /// Number of nodes the engine searches per move
+    #[arg(short, long, default_value_t = 5000)]
+    pub nodes: u64,
+    /// Number of games played in parallel
+    #[arg(short, long, default_value_t = std::thread::available_parallelism().map_or(1, |n| n.get()))]
+    pub threads: usize,
+    /// Number of random plies played at the start of every game
+    #[arg(long, default_value_t = 8)]
+    pub random_plies: usize,
+    /// Start the games from the three-move ballots in turn, the random plies follow the ballot
+    #[arg(long)]
+    pub ballots: bool,
+    /// Games are adjudicated as draws after this many plies
+    #[arg(long, default_value_t = 300)]
+    pub max_plies: usize,
+    /// Seed of the random openings
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// File the positions are written to, one "FEN;score;result" line each
+    #[arg(short, long, default_value = "positions.txt")]
+    pub output: PathBuf,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+impl DatagenArgs {
+    pub fn datagen_config(&self) -> DatagenConfig {
+        DatagenConfig {
+            games: self.games,
+            threads: self.threads,
+            random_plies: self.random_plies,
+            ballots: self.ballots,
+            max_plies: self.max_plies,
+            seed: self.seed,
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "tbgen", about = "Generate endgame tablebases by retrograde analysis")]
+pub struct TbgenArgs {
+    /// Maximum number of pieces on the board
+    #[arg(short, long, default_value_t = 4)]
+    pub pieces: u8,
+    /// Directory the tablebase files are written to
+    #[arg(short, long, default_value = "tablebase")]
+    pub output: PathBuf,
+    /// Write the Huffman coded format that is memory-mapped when probing
+    #[arg(short, long)]
+    pub compress: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "tbverify", about = "Check tablebase files against freshly generated tables")]
+pub struct TbverifyArgs {
+    /// Directory with the tablebase files
+    pub tablebase: PathBuf,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "book", about = "Build an opening book from PDN games or engine analysis")]
+pub struct BookArgs {
+    /// PDN files with the games to add to the book
+    pub games: Vec<PathBuf>,
+    /// File the book is written to
+    #[arg(short, long, default_value = "book.bin")]
+    pub output: PathBuf,
+    /// Number of plies of every game added to the book
+    #[arg(long, default_value_t = 16)]
+    pub plies: usize,
+    /// Add the engine's move in every position reachable within this many plies
+    #[arg(long)]
+    pub analyze: Option<usize>,
+    /// Search depth of the analysis
+    #[arg(short, long, default_value_t = 10)]
+    pub depth: usize,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "match", about = "Play a match between two engine settings on three-move ballots")]
+pub struct MatchArgs {
+    /// Search method of the first player
+    #[arg(long, value_enum, default_value_t = PlayerKind::AlphaBeta)]
+    pub engine: PlayerKind,
+    /// Search method of the second player
+    #[arg(long, value_enum, default_value_t = PlayerKind::AlphaBeta)]
+    pub opponent: PlayerKind,
+    /// Search depth of the first player, if it searches to a fixed depth
+    #[arg(short, long, default_value_t = 6)]
+    pub depth: usize,
+    /// Search depth of the second player, if it searches to a fixed depth
+    #[arg(long, default_value_t = 4)]
+    pub opponent_depth: usize,
+    /// Playouts per move of MCTS players, simulations per move of zero players
+    #[arg(long, default_value_t = 10_000)]
+    pub iterations: u64,
+    /// How MCTS players estimate new leaves of their tree
+    #[arg(long, value_enum, default_value_t = PlayoutKind::Random)]
+    pub playout: PlayoutKind,
+    /// File with the policy/value network of zero players
+    #[arg(long)]
+    pub zero_network: Option<PathBuf>,
+    /// Number of ballots to play, each once with either color (default: all)
+    #[arg(short, long)]
+    pub ballots: Option<usize>,
+    /// Number of the first ballot
+    #[arg(long, default_value_t = 1)]
+    pub first_ballot: usize,
+    /// Games are adjudicated as draws after this many plies
+    #[arg(long, default_value_t = 300)]
+    pub max_plies: usize,
+    /// Give each engine a clock with this many seconds per game instead of a fixed depth.
+    /// The depths then only cap the search
+    #[arg(long)]
+    pub time: Option<f64>,
+    /// Seconds added to the clock after every move
+    #[arg(long, default_value_t = 0.0, requires = "time")]
+    pub increment: f64,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+impl MatchArgs {
+    pub fn match_config(&self) -> MatchConfig {
+        let defaults = MatchConfig::default();
+        MatchConfig {
+            ballots: self.ballots.unwrap_or(defaults.ballots),
+            first_ballot: self.first_ballot,
+            max_plies: self.max_plies,
+            clock: self.time.map(|time| TimeControl {
+                remaining: Duration::from_secs_f64(time),
+                increment: Duration::from_secs_f64(self.increment),
+                moves_to_go: None,
+            }),
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "analyze", about = "Show the best lines of a position with their scores")]
+pub struct AnalyzeArgs {
+    /// The position in FEN notation, e.g. "W:W21-32:B1-12"
+    pub fen: String,
+    /// Number of lines to show
+    #[arg(short, long, default_value_t = 3)]
+    pub multipv: usize,
+    /// Maximum search depth
+    #[arg(short, long, default_value_t = 12)]
+    pub depth: usize,
+    /// Number of search threads
+    #[arg(short, long, default_value_t = 1)]
+    pub threads: usize,
+    /// Directory with endgame tablebase files generated by "shanks tbgen"
+    #[arg(long)]
+    pub tablebase: Option<PathBuf>,
+    /// Print the progress of every search iteration
+    #[arg(short, long)]
+    pub verbose: bool,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+impl AnalyzeArgs {
+    pub fn engine_options(&self) -> EngineOptions {
+        EngineOptions { depth: self.depth, threads: self.threads, multi_pv: self.multipv, ..Default::default() }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "calibrate", about = "Measure the ratings of the skill levels against a reference engine")]
+pub struct CalibrateArgs {
+    /// Number of ballots every level plays, each once with either color
+    #[arg(short, long, default_value_t = 20)]
+    pub ballots: usize,
+    /// Search depth of the full strength reference engine
+    #[arg(long, default_value_t = 6)]
+    pub reference_depth: usize,
+    /// Search depth of the engine the levels are derived from
+    #[arg(short, long, default_value_t = 12)]
+    pub depth: usize,
+    /// Games are adjudicated as draws after this many plies
+    #[arg(long, default_value_t = 200)]
+    pub max_plies: usize,
+    /// Seed of the random choices of the levels
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// The evaluation of both engines, which should be the one the levels are played with
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "testsuite", about = "Check which positions of a test suite the engine solves")]
+pub struct TestsuiteArgs {
+    /// Test suite with positions and best moves, e.g. "W:W21,22:B13 bm 22-18; id \"a\";"
+    /// (default: the shipped regression suite)
+    pub file: Option<PathBuf>,
+    /// Milliseconds to think per position
+    #[arg(short, long, default_value_t = 1000)]
+    pub movetime: u64,
+    /// Maximum search depth
+    #[arg(short, long, default_value_t = 64)]
+    pub depth: usize,
+    /// Number of search threads
+    #[arg(short, long, default_value_t = 1)]
+    pub threads: usize,
+    #[command(flatten)]
+    pub evaluation: EvalSelection,
+}
+
+impl TestsuiteArgs {
+    pub fn engine_options(&self) -> EngineOptions {
+        EngineOptions {
+            depth: self.depth,
+            threads: self.threads,
+            movetime: Some(Duration::from_millis(self.movetime)),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "zero", about = "Train a policy/value network by reinforcement learning from self-play")]
+pub struct ZeroArgs {
+    /// Number of rounds of self-play followed by training
+    #[arg(long, default_value_t = 10)]
+    pub generations: usize,
+    /// Number of self-play games per generation
+    #[arg(short, long, default_value_t = 50)]
+    pub games: usize,
+    /// Number of search simulations per move
+    #[arg(short, long, default_value_t = 100)]
+    pub simulations: u64,
+    /// Number of games played in parallel
+    #[arg(short, long, default_value_t = std::thread::available_parallelism().map_or(1, |n| n.get()))]
+    pub threads: usize,
+    /// Number of plies at the start of every game chosen by visit count with noise, for variety
+    #[arg(long, default_value_t = 16)]
+    pub sampling_plies: usize,
+    /// Self-play games are adjudicated as draws after this many plies
+    #[arg(long, default_value_t = 200)]
+    pub max_plies: usize,
+    /// Size of the hidden layers of a new network
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u32).range(1..=1024))]
+    pub hidden: u32,
+    /// Network to continue training, a new random network is created if omitted
+    #[arg(long)]
+    pub network: Option<PathBuf>,
+    /// File the network is written to after every generation
+    #[arg(short, long, default_value = "zero.bin")]
+    pub output: PathBuf,
+    /// File the self-play samples are appended to, one "FEN;value;policy" line each
+    #[arg(long)]
+    pub samples: Option<PathBuf>,
+    /// Number of the most recent samples the network is trained on
+    #[arg(long, default_value_t = 20_000)]
+    pub window: usize,
+    /// Passes over the samples per generation
+    #[arg(long, default_value_t = 4)]
+    pub epochs: usize,
+    /// Step size of the gradient descent
+    #[arg(long, default_value_t = 0.05)]
+    pub learning_rate: f32,
+    /// Number of samples per gradient descent step
+    #[arg(long, default_value_t = 32)]
+    pub batch_size: usize,
+    /// Number of ballots played against minimax at depth 2 after every generation, each
+    /// once with either color, to show the progress
+    #[arg(long, default_value_t = 2)]
+    pub eval_ballots: usize,
+    /// Seed of the network, the self-play games and the training
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+}
+
+impl ZeroArgs {
+    /// Returns the self-play settings of generation `generation`, counted from zero, which
+    /// continue the seeds where the previous generation stopped.
+    pub fn self_play_config(&self, generation: usize) -> SelfPlayConfig {
+        let defaults = SelfPlayConfig::default();
+        SelfPlayConfig {
+            games: self.games,
+            threads: self.threads,
+            search: ZeroOptions { simulations: self.simulations, ..defaults.search },
+            sampling_plies: self.sampling_plies,
+            max_plies: self.max_plies,
+            seed: self.seed.wrapping_add((generation * self.games) as u64),
+            ..defaults
+        }
+    }
+
+    pub fn trainer(&self) -> ZeroTrainer {
+        ZeroTrainer { learning_rate: self.learning_rate, batch_size: self.batch_size, ..Default::default() }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "solve", about = "Prove whether a position is a win, loss or draw with proof-number search")]
+pub struct SolveArgs {
+    /// The position in FEN notation, e.g. "W:W21-32:B1-12"
+    pub fen: String,
+    /// Memory for the table of proof numbers in MiB
+    #[arg(short, long, default_value_t = 64)]
+    pub memory: usize,
+    /// Give up after expanding this many nodes
+    #[arg(short, long)]
+    pub nodes: Option<u64>,
+}
+
+impl SolveArgs {
+    pub fn solver_options(&self) -> SolverOptions {
+        SolverOptions { memory: self.memory << 20, nodes: self.nodes }
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    time::Instant,
+};
+
+use shanks_core::board::Color;
+use shanks_engine::{generate, Engine, EngineOptions};
+
+use crate::cli::DatagenArgs;
+
+pub fn datagen(args: DatagenArgs) {
+    let network = match (args.evaluation.evaluator(), args.evaluation.network()) {
+        (Ok(_), Ok(network)) => network,
+        (Err(err), _) | (_, Err(err)) => {
+            println!("Failed to load the evaluation: {}", err);
+            return;
+        }
+    };
+    let mut output = match File::create(&args.output) {
+        Ok(file) => BufWriter::new(file),
+        Err(err) => {
+            println!("Failed to create {}: {}", args.output.display(), err);
+            return;
+        }
+    };
+
+    let options = EngineOptions { nodes: Some(args.nodes), tt_size: 1 << 16, ..Default::default() };
+    let make_engine = || {
+        let evaluator = args.evaluation.evaluator().expect("the evaluation was loaded before");
+        let mut engine = Engine::with_evaluator(Color::White, options, evaluator);
+        engine.set_network(network.clone());
+        engine
+    };
+
+    println!("Playing {} games at {} nodes per move on {} threads...", args.games, args.nodes, args.threads);
+    let start = Instant::now();
+    let report_every = (args.games / 20).max(1);
+    let mut games = 0;
+    let mut error = None;
+    let stats = generate(&args.datagen_config(), make_engine, |positions| {
+        for position in positions {
+            if let Err(err) = writeln!(output, "{}", position) {
+                error.get_or_insert(err);
+            }
+        }
+        games += 1;
+        if games % report_every == 0 {
+            println!("{:>8} games {:>8.1}s", games, start.elapsed().as_secs_f64());
+        }
+    });
+
+    if let Some(err) = error.or_else(|| output.flush().err()) {
+        println!("Failed to write the positions: {}", err);
+        return;
+    }
+    println!(
+        "{} games (+{} ={} -{} for white), {} positions, {} duplicates skipped",
+        stats.games, stats.white_wins, stats.draws, stats.black_wins, stats.positions, stats.duplicates
+    );
+    println!("Saved the positions to {}", args.output.display());
+}
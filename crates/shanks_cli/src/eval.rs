@@ -0,0 +1,45 @@
+use shanks_core::board::Board;
+use shanks_engine::Evaluator;
+
+use crate::cli::EvalArgs;
+
+/// Width of the column the board is printed in, the breakdown is printed to its right.
+const BOARD_WIDTH: usize = 22;
+
+pub fn eval(args: EvalArgs) {
+    let board = match Board::from_fen(&args.fen) {
+        Ok(board) => board,
+        Err(err) => {
+            println!("Invalid FEN: {}", err);
+            return;
+        }
+    };
+    let evaluator = match args.evaluation.evaluator() {
+        Ok(evaluator) => evaluator,
+        Err(err) => {
+            println!("Failed to load the evaluation parameters: {}", err);
+            return;
+        }
+    };
+    let breakdown = evaluator.breakdown(board.get_backend());
+
+    let board_text = board.to_string();
+    let breakdown_text = breakdown.to_string();
+    let board_lines: Vec<&str> = board_text.lines().collect();
+    let breakdown_lines: Vec<&str> = breakdown_text.lines().collect();
+    for i in 0..board_lines.len().max(breakdown_lines.len()) {
+        let left = board_lines.get(i).copied().unwrap_or("");
+        let right = breakdown_lines.get(i).copied().unwrap_or("");
+        println!("{:<width$}{}", left, right, width = BOARD_WIDTH);
+    }
+
+    println!();
+    println!("{} to move: {:.3}", board.to_move(), evaluator.evaluate(board.get_backend(), board.to_move()));
+    match args.evaluation.network() {
+        Ok(Some(network)) => {
+            println!("NNUE: {:.3}", network.evaluate(board.get_backend(), board.to_move()));
+        }
+        Ok(None) => {}
+        Err(err) => println!("Failed to load the network: {}", err),
+    }
+}
@@ -1,7 +1,31 @@
+mod analyze;
+mod bench;
+mod book;
+mod calibrate;
 mod cli;
+mod datagen;
 mod debug;
+mod eval;
+mod matches;
 mod play;
+mod solve;
+mod tbgen;
+mod testsuite;
+mod tune;
+mod zero;
 
+pub use analyze::analyze;
+pub use bench::bench;
+pub use book::book;
+pub use calibrate::calibrate;
 pub use cli::{Cli, SubCommand};
+pub use datagen::datagen;
 pub use debug::debug;
+pub use eval::eval;
+pub use matches::matches;
 pub use play::play;
+pub use solve::solve;
+pub use tbgen::{tbgen, tbverify};
+pub use testsuite::testsuite;
+pub use tune::tune;
+pub use zero::zero;
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use shanks_core::board::Color;
+use shanks_engine::{
+    play_match, Engine, EngineOptions, Evaluator, GreedyPlayer, Mcts, MctsOptions, MinimaxPlayer, Network, Player,
+    PolicyValueNet, RandomPlayer, ZeroOptions, ZeroPlayer,
+};
+
+use crate::cli::{MatchArgs, PlayerKind};
+
+pub fn matches(args: MatchArgs) {
+    let (evaluator, network) = match (args.evaluation.evaluator(), args.evaluation.network()) {
+        (Ok(evaluator), Ok(network)) => (evaluator, network),
+        (Err(err), _) | (_, Err(err)) => {
+            println!("Failed to load the evaluation: {}", err);
+            return;
+        }
+    };
+    let zero_network = match &args.zero_network {
+        Some(path) => match PolicyValueNet::load(path) {
+            Ok(network) => Some(Arc::new(network)),
+            Err(err) => {
+                println!("Failed to load {}: {}", path.display(), err);
+                return;
+            }
+        },
+        None if [args.engine, args.opponent].contains(&PlayerKind::Zero) => {
+            println!("Zero players need a network, given with --zero-network");
+            return;
+        }
+        None => None,
+    };
+    let mut first = make_player(&args, args.engine, args.depth, evaluator, &network, &zero_network);
+    let second_evaluator = args.evaluation.evaluator().expect("the evaluation was loaded before");
+    let mut second = make_player(&args, args.opponent, args.opponent_depth, second_evaluator, &network, &zero_network);
+
+    let config = args.match_config();
+    println!("Playing {} ballots with both colors: {} against {}", config.ballots, first.name(), second.name());
+    if let Some(clock) = config.clock {
+        println!("Clock: {:?} per game, {:?} per move", clock.remaining, clock.increment);
+    }
+    let mut games = 0;
+    let stats = play_match(first.as_mut(), second.as_mut(), &config, |game| {
+        games += 1;
+        println!(
+            "{:>4} ballot {:>3} {:<20} first player {:<5} {:>4} plies  {}{}",
+            games,
+            game.ballot.number(),
+            game.ballot.to_string(),
+            game.first_color.to_string(),
+            game.plies,
+            if game.first_result() == 1.0 {
+                "win"
+            } else if game.first_result() == 0.0 {
+                "loss"
+            } else {
+                "draw"
+            },
+            if game.time_forfeit { " on time" } else { "" }
+        );
+    });
+    println!("+{} ={} -{} ({:.1}% for the first player)", stats.wins, stats.draws, stats.losses, stats.score() * 100.0);
+}
+
+fn make_player(
+    args: &MatchArgs,
+    kind: PlayerKind,
+    depth: usize,
+    evaluator: Box<dyn Evaluator>,
+    network: &Option<Network>,
+    zero_network: &Option<Arc<PolicyValueNet>>,
+) -> Box<dyn Player> {
+    match kind {
+        PlayerKind::AlphaBeta => {
+            let options = EngineOptions { depth, ..Default::default() };
+            let mut engine = Engine::with_evaluator(Color::White, options, evaluator);
+            engine.set_network(network.clone());
+            Box::new(engine)
+        }
+        PlayerKind::Mcts => {
+            let options =
+                MctsOptions { iterations: args.iterations, playout: args.playout.into(), ..Default::default() };
+            Box::new(Mcts::with_evaluator(options, evaluator))
+        }
+        PlayerKind::Random => Box::new(RandomPlayer::new()),
+        PlayerKind::Greedy => Box::new(GreedyPlayer::new()),
+        PlayerKind::Minimax => Box::new(MinimaxPlayer::with_evaluator(depth, evaluator)),
+        PlayerKind::Zero => {
+            let network = zero_network.clone().expect("the zero network was loaded before");
+            Box::new(ZeroPlayer::new(network, ZeroOptions { simulations: args.iterations, ..Default::default() }))
+        }
+    }
+}
@@ -1,17 +1,49 @@
-use std::io::Write;
+use std::{io::Write, sync::Arc};
 
-use shanks_core::board::{Board, Color};
-use shanks_engine::Engine;
+use shanks_core::board::{Board, Color, Ply};
+use shanks_engine::{Book, Engine, EngineHandle, SearchEvent, SearchResult, SearchStats, Tablebase};
 
 use crate::cli::PlayArgs;
 
 pub fn play(args: PlayArgs) {
     let mut board = Board::default();
-    let mut engine = Engine::new(Color::White);
+    let (evaluator, network) = match (args.evaluation.evaluator(), args.evaluation.network()) {
+        (Ok(evaluator), Ok(network)) => (evaluator, network),
+        (Err(err), _) | (_, Err(err)) => {
+            println!("Failed to load the evaluation: {}", err);
+            return;
+        }
+    };
+    let tablebase = match args.tablebase.as_ref().map(Tablebase::load).transpose() {
+        Ok(tablebase) => tablebase,
+        Err(err) => {
+            println!("Failed to load the tablebase: {}", err);
+            return;
+        }
+    };
+    let book = match args.book.as_ref().map(Book::load).transpose() {
+        Ok(book) => book,
+        Err(err) => {
+            println!("Failed to load the opening book: {}", err);
+            return;
+        }
+    };
+    let mut engine = Engine::with_evaluator(Color::White, args.engine_options(), evaluator);
+    engine.set_network(network);
+    engine.set_tablebase(tablebase.map(Arc::new));
+    engine.set_book(book.map(Arc::new));
+    engine.set_skill(args.skill());
+    let skill = engine.skill();
+    let mut handle = EngineHandle::new(engine);
+
     print!("{esc}c", esc = 27 as char);
     println!("Playing a game of checkers...");
+    if let Some(skill) = skill {
+        println!("Skill level {} (about {:.0} Elo)", skill, skill.elo());
+    }
     println!("{}", board);
-    println!("Value: {}", engine.evaluate(&board));
+    handle.go(&board);
+    let mut expected = analyze(&mut handle, &board, &args);
     println!("{} to move", board.to_move());
     println!("Legal plies:");
     board.legal_plies();
@@ -48,7 +80,20 @@ pub fn play(args: PlayArgs) {
                 break;
             }
             println!("{}", board);
-            println!("Value: {}", engine.evaluate(&board));
+            match expected.take() {
+                Some(expected) if expected == ply => {
+                    println!("Ponder hit");
+                    handle.ponder_hit();
+                }
+                Some(_) => {
+                    // The ponder search was on the wrong position, its result is of no use.
+                    handle.stop();
+                    handle.wait(std::time::Duration::MAX);
+                    handle.go(&board);
+                }
+                None => handle.go(&board),
+            }
+            expected = analyze(&mut handle, &board, &args);
             println!("{} to move", board.to_move());
             println!("Legal plies:");
             board.legal_plies();
@@ -57,3 +102,47 @@ pub fn play(args: PlayArgs) {
         }
     }
 }
+
+/// Prints the result of the running search of the position on the board. With pondering
+/// enabled, the engine then goes on to search the position after the best ply and the
+/// best ply is returned.
+fn analyze(handle: &mut EngineHandle, board: &Board, args: &PlayArgs) -> Option<Ply> {
+    let (result, stats) = loop {
+        match handle.events().recv() {
+            Ok(SearchEvent::Info(info)) if args.verbose => println!("{}", info),
+            Ok(SearchEvent::Info(_)) => {}
+            Ok(SearchEvent::BestMove { result, stats, .. }) => break (result, stats),
+            Err(_) => return None,
+        }
+    };
+    print_analysis(&result, &stats, board);
+
+    let best = result.best_ply().filter(|_| args.ponder)?.clone();
+    let mut next = board.clone();
+    next.ply(best.clone());
+    handle.ponder(&next);
+    Some(best)
+}
+
+fn print_analysis(result: &SearchResult, stats: &SearchStats, board: &Board) {
+    let value = if board.to_move() == Color::White { result.score } else { -result.score };
+    println!("Value: {}", value);
+    if let Some(ply) = result.best_ply() {
+        println!("Best move: {} ({})", ply.notation(), ply);
+        println!("PV (depth {}): {}", result.depth, result.pv_notation());
+    }
+    if result.tb_hits > 0 {
+        println!("Tablebase hits: {}", result.tb_hits);
+    }
+    if stats.nodes > 0 {
+        println!(
+            "Nodes: {} ({:.0} nps), seldepth {}, TT hits {:.1}%, cutoffs {:.1}% ({:.1}% on the first ply)",
+            stats.nodes,
+            stats.nps(),
+            stats.seldepth,
+            stats.tt_hit_rate() * 100.0,
+            stats.cutoff_rate() * 100.0,
+            stats.first_move_cutoff_rate() * 100.0
+        );
+    }
+}
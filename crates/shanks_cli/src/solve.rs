@@ -0,0 +1,35 @@
+use std::time::Instant;
+
+use shanks_core::board::Board;
+use shanks_engine::{Proof, Solver};
+
+use crate::cli::SolveArgs;
+
+pub fn solve(args: SolveArgs) {
+    let board = match Board::from_fen(&args.fen) {
+        Ok(board) => board,
+        Err(err) => {
+            println!("Invalid FEN: {}", err);
+            return;
+        }
+    };
+
+    println!("{}", board);
+    println!("{} to move", board.to_move());
+    let start = Instant::now();
+    let mut solver = Solver::new(args.solver_options());
+    let result = solver.solve(&board);
+    let elapsed = start.elapsed().as_secs_f64();
+
+    match result.proof {
+        Proof::Win | Proof::Loss => {
+            let winner = if result.proof == Proof::Win { board.to_move() } else { board.to_move().opposite() };
+            println!("{} wins", winner);
+            let line: Vec<String> = result.line.iter().map(|ply| ply.notation()).collect();
+            println!("Line: {}", if line.is_empty() { "-".to_string() } else { line.join(" ") });
+        }
+        Proof::Draw => println!("Draw, neither side can force a win"),
+        Proof::Unknown => println!("Not solved within the limits"),
+    }
+    println!("Nodes: {} ({:.0} nps) in {:.3}s", result.nodes, result.nodes as f64 / elapsed.max(1e-9), elapsed);
+}
@@ -0,0 +1,67 @@
+use std::time::Instant;
+
+use shanks_engine::Tablebase;
+
+use crate::cli::{TbgenArgs, TbverifyArgs};
+
+pub fn tbgen(args: TbgenArgs) {
+    println!("Generating the tablebase for up to {} pieces...", args.pieces);
+    let start = Instant::now();
+    let tablebase = Tablebase::generate(args.pieces, |slice| {
+        let (wins, losses, draws) = slice.counts();
+        println!(
+            "{:<10} {:>10} wins {:>10} losses {:>10} draws  longest {:>3} plies {:>8.1}s",
+            slice.material().to_string(),
+            wins,
+            losses,
+            draws,
+            slice.max_distance(),
+            start.elapsed().as_secs_f64()
+        );
+    });
+
+    match tablebase.save(&args.output, args.compress) {
+        Ok(()) => println!("Saved {} slices to {}", tablebase.len(), args.output.display()),
+        Err(err) => println!("Failed to save the tablebase: {}", err),
+    }
+}
+
+pub fn tbverify(args: TbverifyArgs) {
+    let tablebase = match Tablebase::load(&args.tablebase) {
+        Ok(tablebase) if !tablebase.is_empty() => tablebase,
+        Ok(_) => {
+            println!("No tablebase files found in {}", args.tablebase.display());
+            return;
+        }
+        Err(err) => {
+            println!("Failed to load the tablebase: {}", err);
+            return;
+        }
+    };
+
+    println!("Generating the reference tables for up to {} pieces...", tablebase.max_pieces());
+    let reference = Tablebase::generate(tablebase.max_pieces(), |_| {});
+    let mut failed = 0;
+    for expected in reference.slices() {
+        let material = expected.material();
+        match tablebase.slice(material) {
+            Some(slice) => {
+                let mismatches = slice.mismatches(expected);
+                if mismatches > 0 {
+                    failed += 1;
+                }
+                println!("{:<10} {:>10} mismatches", material.to_string(), mismatches);
+            }
+            None => {
+                failed += 1;
+                println!("{:<10} missing", material.to_string());
+            }
+        }
+    }
+
+    if failed == 0 {
+        println!("All {} slices match", reference.len());
+    } else {
+        println!("{} of {} slices do not match", failed, reference.len());
+    }
+}
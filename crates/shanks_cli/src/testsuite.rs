@@ -0,0 +1,56 @@
+use std::fs;
+
+use shanks_core::board::Color;
+use shanks_engine::{parse_suite, regression_suite, run_suite, Engine};
+
+use crate::cli::TestsuiteArgs;
+
+pub fn testsuite(args: TestsuiteArgs) {
+    let positions = match &args.file {
+        Some(path) => {
+            let text = match fs::read_to_string(path) {
+                Ok(text) => text,
+                Err(err) => {
+                    println!("Failed to read {}: {}", path.display(), err);
+                    return;
+                }
+            };
+            match parse_suite(&text) {
+                Ok(positions) => positions,
+                Err(err) => {
+                    println!("Failed to parse {}: {}", path.display(), err);
+                    return;
+                }
+            }
+        }
+        None => regression_suite(),
+    };
+    let (evaluator, network) = match (args.evaluation.evaluator(), args.evaluation.network()) {
+        (Ok(evaluator), Ok(network)) => (evaluator, network),
+        (Err(err), _) | (_, Err(err)) => {
+            println!("Failed to load the evaluation: {}", err);
+            return;
+        }
+    };
+    let mut engine = Engine::with_evaluator(Color::White, args.engine_options(), evaluator);
+    engine.set_network(network);
+
+    println!("Testing {} positions with {} ms each", positions.len(), args.movetime);
+    let solved = run_suite(&mut engine, &positions, |outcome| {
+        println!(
+            "{:<12} {:<6} played {:<12} expected {:<12} depth {:>3} score {:>9.3}",
+            outcome.position.id,
+            if outcome.solved { "solved" } else { "failed" },
+            outcome.result.best_ply().map_or_else(|| "-".to_string(), |ply| ply.notation()),
+            outcome.position.best_notation(),
+            outcome.result.depth,
+            outcome.result.score
+        );
+    });
+    println!(
+        "Solved {} of {} ({:.1}%)",
+        solved,
+        positions.len(),
+        solved as f64 * 100.0 / positions.len().max(1) as f64
+    );
+}
@@ -0,0 +1,48 @@
+use shanks_engine::{EvalParams, Tuner, TERM_NAMES};
+
+use crate::cli::TuneArgs;
+
+pub fn tune(args: TuneArgs) {
+    let params = match &args.params {
+        Some(path) => EvalParams::load(path),
+        None => Ok(EvalParams::default()),
+    };
+    let params = match params {
+        Ok(params) => params,
+        Err(err) => {
+            println!("Failed to load the evaluation parameters: {}", err);
+            return;
+        }
+    };
+
+    println!("Loading positions from {}...", args.positions.display());
+    let mut tuner = match Tuner::load(&args.positions, &params) {
+        Ok(tuner) if !tuner.is_empty() => tuner,
+        Ok(_) => {
+            println!("No usable positions found");
+            return;
+        }
+        Err(err) => {
+            println!("Failed to load the positions: {}", err);
+            return;
+        }
+    };
+
+    let k = tuner.fit_k(&params);
+    println!("{} positions, k = {:.4}, initial error {:.6}", tuner.len(), k, tuner.error(&params));
+
+    let report_every = (args.epochs / 20).max(1);
+    let tuned = tuner.tune(&params, args.epochs, args.learning_rate, |epoch, error| {
+        if epoch % report_every == 0 {
+            println!("epoch {:>6}: error {:.6}", epoch, error);
+        }
+    });
+
+    for (name, weight) in TERM_NAMES.iter().zip(tuned.weights()) {
+        println!("{:<14}{:>10.4}", name, weight);
+    }
+    match tuned.save(&args.output) {
+        Ok(()) => println!("Saved the tuned weights to {}", args.output.display()),
+        Err(err) => println!("Failed to save the tuned weights: {}", err),
+    }
+}
@@ -0,0 +1,93 @@
+use std::{sync::Arc, time::Instant};
+
+use shanks_engine::{
+    play_match, self_play, write_samples, MatchConfig, MinimaxPlayer, PolicyValueNet, ZeroOptions, ZeroPlayer,
+};
+
+use crate::cli::ZeroArgs;
+
+pub fn zero(args: ZeroArgs) {
+    let mut network = match &args.network {
+        Some(path) => match PolicyValueNet::load(path) {
+            Ok(network) => network,
+            Err(err) => {
+                println!("Failed to load {}: {}", path.display(), err);
+                return;
+            }
+        },
+        None => PolicyValueNet::random(args.hidden as usize, args.seed),
+    };
+    println!(
+        "Training a network with {} hidden units: {} generations of {} games at {} simulations per move",
+        network.hidden(),
+        args.generations,
+        args.games,
+        args.simulations
+    );
+
+    let trainer = args.trainer();
+    let mut window = Vec::new();
+    for generation in 0..args.generations {
+        let start = Instant::now();
+        let current = Arc::new(network.clone());
+        let mut error = None;
+        let stats = self_play(&current, &args.self_play_config(generation), |samples| {
+            if let Some(path) = &args.samples {
+                if let Err(err) = write_samples(path, samples) {
+                    error.get_or_insert(err);
+                }
+            }
+            window.extend_from_slice(samples);
+        });
+        if let Some(err) = error {
+            println!("Failed to write the samples: {}", err);
+            return;
+        }
+        window.drain(..window.len().saturating_sub(args.window));
+        println!(
+            "generation {:>3}: {} games (+{} ={} -{} for white), {} samples in {:.1}s",
+            generation + 1,
+            stats.games,
+            stats.white_wins,
+            stats.draws,
+            stats.black_wins,
+            stats.samples,
+            start.elapsed().as_secs_f64()
+        );
+
+        let seed = args.seed.wrapping_add(generation as u64);
+        trainer.train(&mut network, &window, args.epochs, seed, |epoch, loss| {
+            if epoch == args.epochs {
+                println!(
+                    "                {} samples, policy loss {:.4}, value loss {:.4}",
+                    window.len(),
+                    loss.policy,
+                    loss.value
+                );
+            }
+        });
+        if let Err(err) = network.save(&args.output) {
+            println!("Failed to save the network: {}", err);
+            return;
+        }
+
+        if args.eval_ballots > 0 {
+            let options = ZeroOptions { simulations: args.simulations, ..Default::default() };
+            let mut player = ZeroPlayer::new(Arc::new(network.clone()), options);
+            let mut minimax = MinimaxPlayer::new(2);
+            let config = MatchConfig { ballots: args.eval_ballots, max_plies: args.max_plies, ..Default::default() };
+            let stats = play_match(&mut player, &mut minimax, &config, |_| {});
+            println!(
+                "                against minimax depth 2: +{} ={} -{} ({:.1}%)",
+                stats.wins,
+                stats.draws,
+                stats.losses,
+                stats.score() * 100.0
+            );
+        }
+    }
+    match network.save(&args.output) {
+        Ok(()) => println!("Saved the network to {}", args.output.display()),
+        Err(err) => println!("Failed to save the network: {}", err),
+    }
+}
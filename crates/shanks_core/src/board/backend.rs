@@ -4,7 +4,7 @@ use std::hash::{Hash, Hasher};
 
 use super::{Color, GameState, Piece, Ply, Square};
 
-pub trait Backend: BackendClone {
+pub trait Backend: BackendClone + Send {
     /// Returns the current game state.
     fn get_gamestate(&self) -> GameState;
 
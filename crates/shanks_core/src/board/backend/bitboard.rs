@@ -35,6 +35,17 @@ pub struct BitBoard {
 }
 
 impl BitBoard {
+    /// Creates a board without any pieces on it.
+    pub(crate) fn empty() -> Self {
+        Self {
+            white: BitField::EMPTY,
+            black: BitField::EMPTY,
+            kings: BitField::EMPTY,
+
+            legal_plies: RefCell::new(PliesState::new()),
+        }
+    }
+
     fn get_color_field(&self, color: Color) -> &BitField {
         match color {
             Color::White => &self.white,
@@ -226,7 +237,6 @@ impl Backend for BitBoard {
         {
             let legal_plies = self.legal_plies.borrow();
             if legal_plies.state == self.get_hash() && legal_plies.color == color {
-                println!("Using cached plies");
                 return legal_plies.plies.clone();
             }
         }
@@ -256,7 +266,7 @@ impl Backend for BitBoard {
     }
 
     fn man_count(&self, color: Color) -> u8 {
-        self.get_color_field(color).count() as u8
+        self.get_color_field(color).difference(self.kings).count() as u8
     }
 
     fn king_count(&self, color: Color) -> u8 {
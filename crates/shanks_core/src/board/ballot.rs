@@ -0,0 +1,65 @@
+use std::sync::OnceLock;
+
+use super::{Board, PdnError, PdnGame};
+
+const BALLOTS: &str = include_str!("ballots.txt");
+
+/// A three-move opening as drawn from a ballot deck in tournament play.
+///
+/// The moves are given in standard notation, like in PDN files, and are rotated the same
+/// way when they are replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ballot {
+    number: usize,
+    moves: [&'static str; 3],
+}
+
+impl Ballot {
+    /// Returns all ballots shipped with the crate, numbered from 1.
+    pub fn all() -> &'static [Ballot] {
+        static ALL: OnceLock<Vec<Ballot>> = OnceLock::new();
+        ALL.get_or_init(|| {
+            BALLOTS
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .enumerate()
+                .map(|(index, line)| {
+                    let moves: Vec<&'static str> = line.split_whitespace().collect();
+                    let moves = moves.try_into().expect("every ballot has three moves");
+                    Ballot { number: index + 1, moves }
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the ballot with the given number, starting at 1.
+    pub fn get(number: usize) -> Option<&'static Ballot> {
+        Self::all().get(number.checked_sub(1)?)
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// Returns the moves in standard notation, e.g. `["11-15", "23-19", "8-11"]`.
+    pub fn moves(&self) -> &[&'static str; 3] {
+        &self.moves
+    }
+
+    /// Returns the board after the three moves of the ballot.
+    pub fn board(&self) -> Result<Board, PdnError> {
+        let game = PdnGame { moves: self.moves.iter().map(|ply| ply.to_string()).collect(), ..Default::default() };
+        let mut board = game.start()?;
+        for ply in game.plies()? {
+            board.ply(ply);
+        }
+        Ok(board)
+    }
+}
+
+impl std::fmt::Display for Ballot {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.moves.join(" "))
+    }
+}
@@ -2,9 +2,11 @@ use colored::Colorize;
 
 use super::{
     backend::{bitboard::BitBoard, Backend},
-    Color, GameState, Ply, Square,
+    fen::{format_fen, parse_fen},
+    Color, FenError, GameState, Piece, Ply, Square,
 };
 
+#[derive(Clone)]
 pub struct Board {
     backend: Box<dyn Backend>,
 
@@ -17,6 +19,30 @@ impl Board {
         Self { backend, current_color: Color::White, selected: None }
     }
 
+    /// Creates a board from a position in FEN notation, e.g. `W:W21,22,23:BK1,2,3`.
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let (to_move, pieces) = parse_fen(fen)?;
+        Ok(Self::from_pieces(to_move, &pieces))
+    }
+
+    /// Creates a board with the given pieces on it and `to_move` to move.
+    pub fn from_pieces(to_move: Color, pieces: &[(Square, Piece)]) -> Self {
+        let mut backend = BitBoard::empty();
+        for &(square, piece) in pieces {
+            backend.set_piece(square, piece);
+        }
+        Self { backend: Box::new(backend), current_color: to_move, selected: None }
+    }
+
+    /// Returns the position in FEN notation.
+    pub fn fen(&self) -> String {
+        let pieces: Vec<_> = Square::ALL
+            .iter()
+            .filter_map(|&square| self.backend.get_piece(square).map(|piece| (square, piece)))
+            .collect();
+        format_fen(self.current_color, &pieces)
+    }
+
     /// Carries out the given ply on the board.
     /// The given ply is assumed to be legal and will not be further validated here.
     pub fn ply(&mut self, ply: Ply) {
@@ -45,8 +71,8 @@ impl Board {
         self.current_color
     }
 
-    pub fn get_backend(&self) -> &Box<dyn Backend> {
-        &self.backend
+    pub fn get_backend(&self) -> &dyn Backend {
+        self.backend.as_ref()
     }
 
     pub fn get_backend_mut(&mut self) -> &mut Box<dyn Backend> {
@@ -0,0 +1,96 @@
+use super::{Color, Piece, PieceKind, Square};
+
+/// An error returned when parsing a position in FEN notation fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    /// The FEN does not consist of the side to move and the two piece lists.
+    InvalidFormat,
+    /// The side to move or the color of a piece list is not `W` or `B`.
+    InvalidColor(String),
+    /// A square is not a number between 1 and 32.
+    InvalidSquare(String),
+}
+
+impl std::fmt::Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FenError::InvalidFormat => write!(f, "expected a FEN of the form [WB]:W<squares>:B<squares>"),
+            FenError::InvalidColor(color) => write!(f, "invalid color '{}'", color),
+            FenError::InvalidSquare(square) => write!(f, "invalid square '{}'", square),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// Parses a position in the FEN syntax of the Portable Draughts Notation,
+/// e.g. `W:W21,22,23:BK1,2,3`, where kings are prefixed with `K` and ranges like `21-32`
+/// are accepted as well. Squares and colors are those of Shanks, see [`Square::number`],
+/// so a FEN from a PDN file describes the position turned around.
+pub(crate) fn parse_fen(fen: &str) -> Result<(Color, Vec<(Square, Piece)>), FenError> {
+    let fen = fen.trim().trim_end_matches('.');
+    let mut fields = fen.split(':');
+    let to_move = parse_color(fields.next().ok_or(FenError::InvalidFormat)?)?;
+
+    let mut pieces = Vec::new();
+    for field in fields {
+        let field = field.trim();
+        let mut chars = field.chars();
+        let color = parse_color(&chars.next().ok_or(FenError::InvalidFormat)?.to_string())?;
+        let squares = chars.as_str();
+        if squares.is_empty() {
+            continue;
+        }
+
+        for token in squares.split(',') {
+            let token = token.trim();
+            let (kind, token) = match token.strip_prefix('K') {
+                Some(rest) => (PieceKind::King, rest),
+                None => (PieceKind::Man, token),
+            };
+            let (first, last) = token.split_once('-').unwrap_or((token, token));
+            let invalid = || FenError::InvalidSquare(token.to_string());
+            let first: u8 = first.parse().map_err(|_| invalid())?;
+            let last: u8 = last.parse().map_err(|_| invalid())?;
+            for number in first..=last {
+                let square = Square::from_number(number).ok_or_else(invalid)?;
+                pieces.push((square, Piece::new(color, kind)));
+            }
+        }
+    }
+
+    Ok((to_move, pieces))
+}
+
+/// Formats a position in the FEN syntax of the Portable Draughts Notation, in the
+/// orientation of Shanks.
+pub(crate) fn format_fen(to_move: Color, pieces: &[(Square, Piece)]) -> String {
+    let mut fen = color_char(to_move).to_string();
+    for color in [Color::White, Color::Black] {
+        let mut squares: Vec<_> = pieces.iter().filter(|(_, piece)| piece.color() == color).collect();
+        squares.sort_by_key(|(square, _)| square.number());
+        let squares: Vec<_> = squares
+            .iter()
+            .filter_map(|(square, piece)| {
+                square.number().map(|number| if piece.is_king() { format!("K{}", number) } else { number.to_string() })
+            })
+            .collect();
+        fen.push_str(&format!(":{}{}", color_char(color), squares.join(",")));
+    }
+    fen
+}
+
+fn parse_color(color: &str) -> Result<Color, FenError> {
+    match color.trim() {
+        "W" | "w" => Ok(Color::White),
+        "B" | "b" => Ok(Color::Black),
+        other => Err(FenError::InvalidColor(other.to_string())),
+    }
+}
+
+fn color_char(color: Color) -> char {
+    match color {
+        Color::White => 'W',
+        Color::Black => 'B',
+    }
+}
@@ -1,13 +1,20 @@
 mod backend;
+mod ballot;
+#[allow(clippy::module_inception)]
 mod board;
+mod fen;
 mod gamestate;
+mod pdn;
 mod piece;
 mod ply;
 mod square;
 
 pub use backend::{Backend, BackendClone};
+pub use ballot::Ballot;
 pub use board::Board;
+pub use fen::FenError;
 pub use gamestate::GameState;
+pub use pdn::{parse_pdn, PdnError, PdnGame};
 pub use piece::{Color, Piece, PieceKind};
 pub use ply::{Ply, PlyBuilder};
 pub use square::Square;
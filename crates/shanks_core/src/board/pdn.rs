@@ -0,0 +1,183 @@
+use super::{fen::parse_fen, Board, FenError, Piece, Ply, Square};
+
+/// An error returned when reading a game in Portable Draughts Notation fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdnError {
+    /// A tag pair is not of the form `[Name "Value"]`.
+    InvalidTag(String),
+    /// The position of the `FEN` tag is invalid.
+    InvalidFen(FenError),
+    /// A move is not legal in the position it is played in.
+    IllegalMove(String),
+}
+
+impl std::fmt::Display for PdnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PdnError::InvalidTag(tag) => write!(f, "invalid tag '{}'", tag),
+            PdnError::InvalidFen(err) => write!(f, "invalid FEN tag: {}", err),
+            PdnError::IllegalMove(ply) => write!(f, "illegal move '{}'", ply),
+        }
+    }
+}
+
+impl std::error::Error for PdnError {}
+
+/// A game read from a PDN file.
+///
+/// PDN files follow the standard orientation, where Black starts on squares 1-12 and
+/// moves first. Shanks lets White move first from squares 21-32, so games are rotated
+/// when they are replayed: square `n` becomes `33 - n` and the colors are swapped.
+/// Results are given for the side that moves first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdnGame {
+    pub tags: Vec<(String, String)>,
+    /// The moves in standard notation, e.g. `11-15` or `15x24`.
+    pub moves: Vec<String>,
+    /// The result from the point of view of the side that moves first: 1 for a win,
+    /// 0.5 for a draw and 0 for a loss, or `None` if it is unknown.
+    pub result: Option<f64>,
+}
+
+impl PdnGame {
+    /// Returns the value of the tag with the given name.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the starting position of the game, taken from the `FEN` tag if present.
+    pub fn start(&self) -> Result<Board, PdnError> {
+        let Some(fen) = self.tag("FEN") else {
+            return Ok(Board::default());
+        };
+        let (to_move, pieces) = parse_fen(fen).map_err(PdnError::InvalidFen)?;
+        let pieces: Vec<_> = pieces
+            .into_iter()
+            .map(|(square, piece)| (rotate(square), Piece::new(piece.color().opposite(), piece.kind())))
+            .collect();
+        Ok(Board::from_pieces(to_move.opposite(), &pieces))
+    }
+
+    /// Replays the game and returns its plies, starting from [`PdnGame::start`].
+    pub fn plies(&self) -> Result<Vec<Ply>, PdnError> {
+        let mut board = self.start()?;
+        let mut plies = Vec::with_capacity(self.moves.len());
+        for token in &self.moves {
+            let ply = find_ply(&board, token).ok_or_else(|| PdnError::IllegalMove(token.clone()))?;
+            board.ply(ply.clone());
+            plies.push(ply);
+        }
+        Ok(plies)
+    }
+}
+
+/// Parses all games of a PDN file. Comments, variations and move numbers are skipped,
+/// the moves are only checked for legality by [`PdnGame::plies`].
+pub fn parse_pdn(text: &str) -> Result<Vec<PdnGame>, PdnError> {
+    let mut games = Vec::new();
+    let mut game = PdnGame::default();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '[' => {
+                // A tag after the moves starts a new game, even without a result.
+                if !game.moves.is_empty() {
+                    games.push(std::mem::take(&mut game));
+                }
+                let end = text[start..].find(']').map_or(text.len(), |end| start + end);
+                game.tags.push(parse_tag(&text[start + 1..end])?);
+                while chars.next_if(|&(index, _)| index <= end).is_some() {}
+            }
+            '{' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' => {
+                let mut depth = 1;
+                for (_, c) in chars.by_ref() {
+                    match c {
+                        '(' => depth += 1,
+                        ')' if depth == 1 => break,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some((index, c)) = chars.next_if(|&(_, c)| !c.is_whitespace() && !"[{(".contains(c)) {
+                    end = index + c.len_utf8();
+                }
+                let token = &text[start..end];
+                match parse_result(token) {
+                    Some(result) => {
+                        game.result = result;
+                        games.push(std::mem::take(&mut game));
+                    }
+                    None => {
+                        // Move numbers like `1.` or `1...` may be glued to the move.
+                        let token = token.rsplit('.').next().unwrap_or(token);
+                        let token = token.trim_end_matches(|c: char| !c.is_ascii_digit());
+                        if !token.is_empty() && token.contains(['-', 'x']) {
+                            game.moves.push(token.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if !game.moves.is_empty() {
+        games.push(game);
+    }
+    Ok(games)
+}
+
+fn parse_tag(tag: &str) -> Result<(String, String), PdnError> {
+    let invalid = || PdnError::InvalidTag(tag.to_string());
+    let (name, value) = tag.trim().split_once(char::is_whitespace).ok_or_else(invalid)?;
+    let value = value.trim().strip_prefix('"').and_then(|value| value.strip_suffix('"')).ok_or_else(invalid)?;
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Returns the result a token stands for, `Some(None)` for an unknown result and `None`
+/// if the token is not a result.
+fn parse_result(token: &str) -> Option<Option<f64>> {
+    match token {
+        "1-0" | "2-0" => Some(Some(1.0)),
+        "0-1" | "0-2" => Some(Some(0.0)),
+        "1/2-1/2" | "1-1" => Some(Some(0.5)),
+        "*" => Some(None),
+        _ => None,
+    }
+}
+
+/// Returns the legal ply a move in standard notation stands for.
+fn find_ply(board: &Board, token: &str) -> Option<Ply> {
+    let squares = token
+        .split(['-', 'x'])
+        .map(|number| number.parse().ok().and_then(Square::from_number).map(rotate))
+        .collect::<Option<Vec<Square>>>()?;
+    let (first, last) = (*squares.first()?, *squares.last()?);
+
+    board.get_backend().get_legal_plies(board.to_move()).into_iter().find(|ply| {
+        let path = ply.path();
+        // Captures may be written with their landing squares or just from and to.
+        ply.from() == first && path.last() == Some(&last) && (squares.len() == 2 || path == squares)
+    })
+}
+
+/// Turns a square around, which maps the standard orientation to the one used by Shanks.
+fn rotate(square: Square) -> Square {
+    square.flipped()
+}
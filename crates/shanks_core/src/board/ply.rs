@@ -34,6 +34,35 @@ impl Ply {
     pub fn captures(&self) -> &[Square] {
         &self.captures
     }
+
+    /// Returns all squares the piece lands on, starting with the origin and ending
+    /// with the destination. For a simple move these are just `from` and `to`.
+    pub fn path(&self) -> Vec<Square> {
+        let mut path = vec![self.from];
+        for capture in &self.captures {
+            let last = path[path.len() - 1];
+            let dx = capture.file() as i8 - last.file() as i8;
+            let dy = capture.rank() as i8 - last.rank() as i8;
+            if let Some(landing) = capture.moved_by(dx, dy) {
+                path.push(landing);
+            }
+        }
+        if path.len() == 1 {
+            path.push(self.to);
+        }
+        path
+    }
+
+    /// Returns the ply in checkers notation with the square numbers of
+    /// [`Square::number`], e.g. `22-18` for a move or `15x24x31` for a capture sequence.
+    pub fn notation(&self) -> String {
+        let separator = if self.captures.is_empty() { "-" } else { "x" };
+        self.path()
+            .iter()
+            .map(|square| square.number().map_or_else(|| square.to_string(), |number| number.to_string()))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -94,10 +94,45 @@ impl Square {
         (self.rank * 8 + self.file) as usize
     }
 
+    /// Returns `true` if the square is one of the 32 dark squares pieces can stand on.
+    pub fn is_playable(&self) -> bool {
+        (self.file + self.rank).is_multiple_of(2)
+    }
+
+    /// Returns the number of the square (1-32), counted from the top left of the board,
+    /// or `None` for a light square.
+    ///
+    /// This is the numbering of all notation in Shanks, including FEN, plies and test
+    /// suites. White moves first and starts on squares 21-32, so it is standard checkers
+    /// numbering turned around: in standard notation the side that moves first is Black
+    /// on squares 1-12. Standard square `n` is Shanks square `33 - n` with the colors
+    /// swapped, which is how PDN games and ballots are read.
+    pub fn number(&self) -> Option<u8> {
+        if !self.is_playable() {
+            return None;
+        }
+        Some((7 - self.rank) * 4 + self.file / 2 + 1)
+    }
+
+    /// Returns the square with the given number (1-32), see [`Square::number`].
+    pub fn from_number(number: u8) -> Option<Square> {
+        if !(1..=32).contains(&number) {
+            return None;
+        }
+        let rank = 7 - (number - 1) / 4;
+        let file = (number - 1) % 4 * 2 + rank % 2;
+        Some(Square { file, rank })
+    }
+
+    /// Returns the square as seen from the other side of the board.
+    pub fn flipped(&self) -> Square {
+        Square { file: 7 - self.file, rank: 7 - self.rank }
+    }
+
     pub fn moved_by(&self, file: i8, rank: i8) -> Option<Square> {
         let new_file = self.file as i8 + file;
         let new_rank = self.rank as i8 + rank;
-        if new_file < 0 || new_file > 7 || new_rank < 0 || new_rank > 7 {
+        if !(0..=7).contains(&new_file) || !(0..=7).contains(&new_rank) {
             return None;
         }
         Some(Square { file: new_file as u8, rank: new_rank as u8 })
@@ -106,7 +141,7 @@ impl Square {
 
 impl std::fmt::Display for Square {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let file = (self.file + 'a' as u8) as char;
+        let file = (self.file + b'a') as char;
         let rank = self.rank + 1;
         write!(f, "{}{}", file, rank)
     }
@@ -0,0 +1,12 @@
+use shanks_core::board::{Board, Color};
+
+#[test]
+fn men_and_kings_are_counted_apart() {
+    let board = Board::from_fen("W:WK5,K14,22,30:B1,10,K19").unwrap();
+    let backend = board.get_backend();
+    assert_eq!((backend.man_count(Color::White), backend.king_count(Color::White)), (2, 2));
+    assert_eq!((backend.man_count(Color::Black), backend.king_count(Color::Black)), (2, 1));
+
+    let board = Board::default();
+    assert_eq!((board.get_backend().man_count(Color::White), board.get_backend().king_count(Color::White)), (12, 0));
+}
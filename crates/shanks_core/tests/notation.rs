@@ -0,0 +1,31 @@
+use shanks_core::board::{Board, Square};
+
+fn notations(fen: &str) -> Vec<String> {
+    let board = Board::from_fen(fen).unwrap();
+    let mut notations: Vec<String> =
+        board.get_backend().get_legal_plies(board.to_move()).iter().map(|ply| ply.notation()).collect();
+    notations.sort();
+    notations
+}
+
+#[test]
+fn square_numbers_round_trip() {
+    let playable: Vec<Square> = Square::ALL.into_iter().filter(Square::is_playable).collect();
+    assert_eq!(playable.len(), 32);
+    for square in playable {
+        let number = square.number().unwrap();
+        assert!((1..=32).contains(&number));
+        assert_eq!(Square::from_number(number), Some(square));
+    }
+    assert!(Square::ALL.into_iter().filter(|square| !square.is_playable()).all(|square| square.number().is_none()));
+    assert_eq!(Square::from_number(0), None);
+    assert_eq!(Square::from_number(33), None);
+}
+
+#[test]
+fn plies_are_written_in_standard_notation() {
+    assert_eq!(notations("W:W21-32:B1-12"), ["21-17", "22-17", "22-18", "23-18", "23-19", "24-19", "24-20"]);
+    assert_eq!(notations("B:W21-32:B1-12"), ["10-14", "10-15", "11-15", "11-16", "12-16", "9-13", "9-14"]);
+    assert_eq!(notations("W:W22:B18"), ["22x15"]);
+    assert_eq!(notations("W:W27:B16,24"), ["27x20x11"]);
+}
@@ -0,0 +1,157 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use shanks_core::board::{Backend, Board, Color, Ply};
+
+use crate::{
+    eval::{Evaluator, MaterialEvaluator},
+    player::Player,
+    search::{MAX_PLY, WIN_SCORE},
+    SearchResult,
+};
+
+/// A player choosing uniformly at random among the legal plies, the weakest baseline.
+pub struct RandomPlayer {
+    rng: StdRng,
+}
+
+impl RandomPlayer {
+    pub fn new() -> Self {
+        Self { rng: StdRng::from_entropy() }
+    }
+
+    /// Seeds the random choices, which makes games reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl Default for RandomPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for RandomPlayer {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn think(&mut self, board: &Board) -> SearchResult {
+        let plies = board.get_backend().get_legal_plies(board.to_move());
+        let pv = plies.choose(&mut self.rng).cloned().into_iter().collect();
+        SearchResult { score: 0.0, depth: 0, pv, nodes: 0, tb_hits: 0 }
+    }
+}
+
+/// A player choosing the ply that leaves it with the best material balance, without
+/// looking at the reply. Ties are broken at random.
+pub struct GreedyPlayer {
+    rng: StdRng,
+}
+
+impl GreedyPlayer {
+    pub fn new() -> Self {
+        Self { rng: StdRng::from_entropy() }
+    }
+
+    /// Seeds the random choices, which makes games reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl Default for GreedyPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for GreedyPlayer {
+    fn name(&self) -> String {
+        "greedy".to_string()
+    }
+
+    fn think(&mut self, board: &Board) -> SearchResult {
+        let (backend, color) = (board.get_backend(), board.to_move());
+        let scored: Vec<(f64, Ply)> = backend
+            .get_legal_plies(color)
+            .into_iter()
+            .map(|ply| {
+                let mut child = backend.clone_box();
+                child.ply(ply.clone());
+                (MaterialEvaluator.evaluate(child.as_ref(), color), ply)
+            })
+            .collect();
+        let best = scored.iter().map(|(score, _)| *score).fold(f64::NEG_INFINITY, f64::max);
+        let candidates: Vec<_> = scored.into_iter().filter(|(score, _)| *score == best).collect();
+        match candidates.choose(&mut self.rng) {
+            Some((score, ply)) => SearchResult {
+                score: *score,
+                depth: 1,
+                pv: vec![ply.clone()],
+                nodes: candidates.len() as u64,
+                tb_hits: 0,
+            },
+            None => SearchResult { score: -WIN_SCORE, depth: 0, pv: Vec::new(), nodes: 0, tb_hits: 0 },
+        }
+    }
+}
+
+/// A plain fixed-depth minimax search without pruning, move ordering or a transposition
+/// table, evaluating the leaves statically. It serves as the reference the alpha-beta
+/// [`Engine`](crate::Engine) has to beat and is only practical for small depths.
+pub struct MinimaxPlayer {
+    depth: usize,
+    evaluator: Box<dyn Evaluator>,
+    nodes: u64,
+}
+
+impl MinimaxPlayer {
+    pub fn new(depth: usize) -> Self {
+        Self::with_evaluator(depth, Box::new(MaterialEvaluator))
+    }
+
+    pub fn with_evaluator(depth: usize, evaluator: Box<dyn Evaluator>) -> Self {
+        Self { depth: depth.max(1), evaluator, nodes: 0 }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the score of the position for `color`, the side to move, and the best line.
+    fn minimax(&mut self, backend: &dyn Backend, color: Color, depth: usize, ply: usize) -> (f64, Vec<Ply>) {
+        self.nodes += 1;
+        let plies = backend.get_legal_plies(color);
+        if plies.is_empty() {
+            return (-(WIN_SCORE - ply as f64), Vec::new());
+        }
+        if depth == 0 || ply >= MAX_PLY {
+            return (self.evaluator.evaluate(backend, color), Vec::new());
+        }
+
+        let mut best = (f64::NEG_INFINITY, Vec::new());
+        for child_ply in plies {
+            let mut child = backend.clone_box();
+            child.ply(child_ply.clone());
+            let (score, line) = self.minimax(child.as_ref(), color.opposite(), depth - 1, ply + 1);
+            if -score > best.0 {
+                let mut pv = vec![child_ply];
+                pv.extend(line);
+                best = (-score, pv);
+            }
+        }
+        best
+    }
+}
+
+impl Player for MinimaxPlayer {
+    fn name(&self) -> String {
+        format!("minimax depth {}", self.depth)
+    }
+
+    fn think(&mut self, board: &Board) -> SearchResult {
+        self.nodes = 0;
+        let (score, pv) = self.minimax(board.get_backend(), board.to_move(), self.depth, 0);
+        SearchResult { score, depth: self.depth, pv, nodes: self.nodes, tb_hits: 0 }
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use shanks_core::board::{Backend, Board, Color, PdnError, PdnGame, Ply, Square};
+
+use crate::Engine;
+
+const MAGIC: &[u8; 4] = b"SHBK";
+const VERSION: u32 = 1;
+/// Size of a move in the book file.
+const ENTRY_SIZE: usize = 28;
+
+/// Random numbers for every piece on every square, plus one for black to move.
+const ZOBRIST: [u64; 4 * 32 + 1] = zobrist_keys();
+
+const fn zobrist_keys() -> [u64; 4 * 32 + 1] {
+    let mut keys = [0; 4 * 32 + 1];
+    let mut state: u64 = 0x5348_414e_4b53_424b;
+    let mut i = 0;
+    while i < keys.len() {
+        // SplitMix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    keys
+}
+
+/// A move of the opening book together with the statistics of the games it was played in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookMove {
+    /// The square the piece moves from, numbered like [`Square::number`].
+    pub from: u8,
+    /// The square the piece ends on, numbered like [`Square::number`].
+    pub to: u8,
+    /// How often the move is chosen relative to the other moves of the position.
+    pub weight: u32,
+    /// Results of the games with the move, from the point of view of the side playing it.
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl BookMove {
+    /// Returns `true` if the move stands for the given ply.
+    pub fn matches(&self, ply: &Ply) -> bool {
+        ply.from().number() == Some(self.from) && ply.to().number() == Some(self.to)
+    }
+}
+
+/// An opening book, mapping positions to the moves played in them.
+///
+/// Positions are identified by Zobrist keys, which stay the same across builds and
+/// platforms, so books can be shared.
+#[derive(Debug, Clone, Default)]
+pub struct Book {
+    positions: HashMap<u64, Vec<BookMove>>,
+}
+
+impl Book {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the Zobrist key of the position with `color` to move.
+    pub fn key(backend: &dyn Backend, color: Color) -> u64 {
+        let mut key = if color == Color::Black { ZOBRIST[4 * 32] } else { 0 };
+        for number in 1..=32 {
+            let square = Square::from_number(number).expect("squares are numbered 1-32");
+            if let Some(piece) = backend.get_piece(square) {
+                let kind = piece.color() as usize * 2 + piece.kind() as usize;
+                key ^= ZOBRIST[kind * 32 + number as usize - 1];
+            }
+        }
+        key
+    }
+
+    /// Returns the number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the moves of the position on the board.
+    pub fn moves(&self, board: &Board) -> &[BookMove] {
+        self.positions.get(&Self::key(board.get_backend(), board.to_move())).map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds a ply played in the position on the board. `result` is the result of the game
+    /// from the point of view of the side to move, if it is known.
+    pub fn add(&mut self, board: &Board, ply: &Ply, weight: u32, result: Option<f64>) {
+        let (Some(from), Some(to)) = (ply.from().number(), ply.to().number()) else {
+            return;
+        };
+        let moves = self.positions.entry(Self::key(board.get_backend(), board.to_move())).or_default();
+        let index = match moves.iter().position(|book_move| book_move.from == from && book_move.to == to) {
+            Some(index) => index,
+            None => {
+                moves.push(BookMove { from, to, weight: 0, wins: 0, draws: 0, losses: 0 });
+                moves.len() - 1
+            }
+        };
+        let book_move = &mut moves[index];
+        book_move.weight += weight;
+        match result {
+            Some(result) if result > 0.5 => book_move.wins += 1,
+            Some(result) if result < 0.5 => book_move.losses += 1,
+            Some(_) => book_move.draws += 1,
+            None => {}
+        }
+    }
+
+    /// Adds the first `max_plies` plies of a game, every ply with a weight of one.
+    pub fn add_game(&mut self, game: &PdnGame, max_plies: usize) -> Result<(), PdnError> {
+        let mut board = game.start()?;
+        let first = board.to_move();
+        for ply in game.plies()?.into_iter().take(max_plies) {
+            let result = game.result.map(|result| if board.to_move() == first { result } else { 1.0 - result });
+            self.add(&board, &ply, 1, result);
+            board.ply(ply);
+        }
+        Ok(())
+    }
+
+    /// Adds the move the engine chooses in every position reachable from the board within
+    /// `plies` plies. The number of positions grows exponentially with `plies`. The engine
+    /// should not have a book of its own, or it will just play the book moves.
+    pub fn add_analysis(&mut self, engine: &mut Engine, board: &Board, plies: usize) {
+        if plies == 0 {
+            return;
+        }
+        if let Some(best) = engine.think(board).best_ply() {
+            self.add(board, best, 1, None);
+        }
+        for ply in board.get_backend().get_legal_plies(board.to_move()) {
+            let mut child = board.clone();
+            child.ply(ply);
+            self.add_analysis(engine, &child, plies - 1);
+        }
+    }
+
+    /// Picks one of the book moves of the position at random, weighted by their weights.
+    /// Returns `None` if the position is not in the book.
+    pub fn pick(&self, board: &Board, rng: &mut impl Rng) -> Option<Ply> {
+        let plies = board.get_backend().get_legal_plies(board.to_move());
+        let candidates: Vec<(Ply, u32)> = self
+            .moves(board)
+            .iter()
+            .filter(|book_move| book_move.weight > 0)
+            .filter_map(|book_move| {
+                let ply = plies.iter().find(|ply| book_move.matches(ply))?;
+                Some((ply.clone(), book_move.weight))
+            })
+            .collect();
+        let distribution = WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(candidates[distribution.sample(rng)].0.clone())
+    }
+
+    /// Reads a book written by [`Book::save`].
+    ///
+    /// The file starts with the magic bytes `SHBK`, a little-endian `u32` version and the
+    /// number of moves as a `u64`. Every move takes 28 bytes: the key of its position as a
+    /// `u64`, the from and to squares, two reserved bytes and the weight, wins, draws and
+    /// losses as `u32`s. Moves are sorted by key.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+        let mut header = [0; 16];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not an opening book"));
+        }
+        if u32::from_le_bytes(header[4..8].try_into().expect("4 bytes")) != VERSION {
+            return Err(invalid("unsupported opening book version"));
+        }
+        let count = u64::from_le_bytes(header[8..16].try_into().expect("8 bytes"));
+
+        let mut book = Self::new();
+        let mut entry = [0; ENTRY_SIZE];
+        for _ in 0..count {
+            reader.read_exact(&mut entry)?;
+            let u32_at = |at: usize| u32::from_le_bytes(entry[at..at + 4].try_into().expect("4 bytes"));
+            let key = u64::from_le_bytes(entry[..8].try_into().expect("8 bytes"));
+            book.positions.entry(key).or_default().push(BookMove {
+                from: entry[8],
+                to: entry[9],
+                weight: u32_at(12),
+                wins: u32_at(16),
+                draws: u32_at(20),
+                losses: u32_at(24),
+            });
+        }
+        Ok(book)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let mut keys: Vec<u64> = self.positions.keys().copied().collect();
+        keys.sort_unstable();
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.positions.values().map(Vec::len).sum::<usize>() as u64).to_le_bytes())?;
+        for key in keys {
+            for book_move in &self.positions[&key] {
+                writer.write_all(&key.to_le_bytes())?;
+                writer.write_all(&[book_move.from, book_move.to, 0, 0])?;
+                for value in [book_move.weight, book_move.wins, book_move.draws, book_move.losses] {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+        }
+        writer.flush()
+    }
+}
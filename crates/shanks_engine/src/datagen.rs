@@ -0,0 +1,183 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use shanks_core::board::{Ballot, Board, Color};
+
+use crate::{search::MAX_PLY, tt::TranspositionTable, Engine, WIN_SCORE};
+
+/// Settings of a self-play data generation run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatagenConfig {
+    /// Number of games to play.
+    pub games: usize,
+    /// Number of games played in parallel.
+    pub threads: usize,
+    /// Number of random plies played from the starting position before the engines take over.
+    pub random_plies: usize,
+    /// Start the games from the three-move ballots in turn instead of the initial position.
+    /// The random plies are played after the ballot.
+    pub ballots: bool,
+    /// Games still running after this many plies are adjudicated as draws.
+    pub max_plies: usize,
+    /// Seed of the random openings. Game `i` always starts from the same opening.
+    pub seed: u64,
+}
+
+impl Default for DatagenConfig {
+    fn default() -> Self {
+        Self { games: 1000, threads: 1, random_plies: 8, ballots: false, max_plies: 300, seed: 0 }
+    }
+}
+
+/// A position from a self-play game, labelled with the search score and the final result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingPosition {
+    pub fen: String,
+    /// The search score from white's point of view.
+    pub score: f64,
+    /// The result of the game from white's point of view: 1 for a win, 0.5 for a draw, 0 for a loss.
+    pub result: f64,
+}
+
+/// Formats the position as a `FEN;score;result` line, which [`Tuner::load`](crate::Tuner::load) reads back.
+impl fmt::Display for TrainingPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{};{:.4};{}", self.fen, self.score, self.result)
+    }
+}
+
+/// Totals of a data generation run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DatagenStats {
+    pub games: usize,
+    pub white_wins: usize,
+    pub black_wins: usize,
+    pub draws: usize,
+    /// Number of positions handed to the sink.
+    pub positions: usize,
+    /// Number of positions dropped because they already occurred earlier in the run.
+    pub duplicates: usize,
+}
+
+/// Plays `config.games` engine-vs-engine games and hands the positions of every finished
+/// game to `sink`.
+///
+/// Every worker thread creates its own engine with `make_engine`, so the engine options
+/// (e.g. a node limit) decide how strong and how fast the games are. Positions in which
+/// the side to move has to capture are skipped, since their static evaluation says little
+/// about the outcome, and positions seen before in the run are dropped.
+pub fn generate<M, S>(config: &DatagenConfig, make_engine: M, mut sink: S) -> DatagenStats
+where
+    M: Fn() -> Engine + Sync,
+    S: FnMut(&[TrainingPosition]),
+{
+    let next_game = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..config.threads.max(1) {
+            let sender = sender.clone();
+            let (make_engine, next_game) = (&make_engine, &next_game);
+            scope.spawn(move || {
+                let mut engine = make_engine();
+                loop {
+                    let game = next_game.fetch_add(1, Ordering::Relaxed);
+                    if game >= config.games {
+                        break;
+                    }
+                    if sender.send(play_game(&mut engine, config, game)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut stats = DatagenStats::default();
+        let mut seen = HashSet::new();
+        for (result, positions) in receiver {
+            stats.games += 1;
+            if result == 1.0 {
+                stats.white_wins += 1;
+            } else if result == 0.0 {
+                stats.black_wins += 1;
+            } else {
+                stats.draws += 1;
+            }
+
+            let count = positions.len();
+            let positions: Vec<_> = positions
+                .into_iter()
+                .filter(|(key, _)| seen.insert(*key))
+                .map(|(_, position)| TrainingPosition { result, ..position })
+                .collect();
+            stats.duplicates += count - positions.len();
+            stats.positions += positions.len();
+            sink(&positions);
+        }
+        stats
+    })
+}
+
+/// Plays game number `game` from a random opening and returns its result from white's
+/// point of view together with the quiet positions of the game, keyed by position and
+/// side to move. The results of the positions are filled in by the caller.
+fn play_game(engine: &mut Engine, config: &DatagenConfig, game: usize) -> (f64, Vec<(u64, TrainingPosition)>) {
+    let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(game as u64));
+    let mut board = if config.ballots {
+        let ballots = Ballot::all();
+        ballots[game % ballots.len()].board().expect("the shipped ballots are legal")
+    } else {
+        Board::default()
+    };
+    engine.clear();
+
+    let mut positions = Vec::new();
+    for ply_count in 0..config.max_plies {
+        let color = board.to_move();
+        let plies = board.get_backend().get_legal_plies(color);
+        if plies.is_empty() {
+            return (loss_for(color), positions);
+        }
+
+        if ply_count < config.random_plies {
+            let ply = plies.choose(&mut rng).expect("plies is not empty").clone();
+            board.ply(ply);
+            continue;
+        }
+
+        let result = engine.think(&board);
+        let score = if color == Color::White { result.score } else { -result.score };
+        if !plies.iter().any(|ply| !ply.captures().is_empty()) {
+            let key = TranspositionTable::key(board.get_backend().state_hash(), color);
+            positions.push((key, TrainingPosition { fen: board.fen(), score, result: 0.5 }));
+        }
+
+        // A forced win was found, there is no need to play it out.
+        if result.score.abs() >= WIN_SCORE - MAX_PLY as f64 {
+            let loser = if score > 0.0 { Color::Black } else { Color::White };
+            return (loss_for(loser), positions);
+        }
+        match result.best_ply() {
+            Some(ply) => board.ply(ply.clone()),
+            None => return (loss_for(color), positions),
+        }
+    }
+    (0.5, positions)
+}
+
+/// Returns the result from white's point of view when `color` loses.
+fn loss_for(color: Color) -> f64 {
+    match color {
+        Color::White => 0.0,
+        Color::Black => 1.0,
+    }
+}
@@ -1,114 +1,386 @@
-use shanks_core::board::{Backend, Board, Color};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    thread,
+    time::Instant,
+};
 
-use crate::static_eval;
+use rand::{rngs::StdRng, SeedableRng};
+use shanks_core::board::{Backend, Board, Color, Ply};
 
-const SEARCH_DEPTH: usize = 16;
+use crate::{
+    book::Book,
+    eval::{Evaluator, MaterialEvaluator},
+    nnue::Network,
+    search::{tablebase_score, SearchContext, SearchResult, Searcher},
+    skill::Skill,
+    stats::{InfoCallback, SearchStats},
+    tablebase::{Outcome, Tablebase},
+    time::{TimeControl, TimeLimits, TimeManager},
+    tt::TranspositionTable,
+    EngineOptions,
+};
 
 pub struct Engine {
     maximizing_color: Color,
+    options: EngineOptions,
+    evaluator: Box<dyn Evaluator>,
+    network: Option<Network>,
+    tablebase: Option<Arc<Tablebase>>,
+    book: Option<Arc<Book>>,
+    skill: Option<Skill>,
 
-    seen_states: std::collections::HashMap<u64, f64>,
+    tt: TranspositionTable,
+    rng: StdRng,
+    on_info: Option<InfoCallback>,
+    abort: Option<Arc<AtomicBool>>,
+    /// The time limits of the running search.
+    time: Option<TimeManager>,
+    stats: SearchStats,
 }
 
 impl Engine {
     pub fn new(maximizing_color: Color) -> Self {
-        Self { maximizing_color, seen_states: std::collections::HashMap::new() }
+        Self::with_options(maximizing_color, EngineOptions::default())
     }
 
-    pub fn evaluate(&mut self, board: &Board) -> f64 {
-        self.search(board.get_backend(), SEARCH_DEPTH)
+    pub fn with_options(maximizing_color: Color, options: EngineOptions) -> Self {
+        Self::with_evaluator(maximizing_color, options, Box::new(MaterialEvaluator))
     }
 
-    pub fn search(&mut self, backend: &Box<dyn Backend>, depth: usize) -> f64 {
-        if depth == 0 {
-            return static_eval::static_eval(backend, self.maximizing_color);
+    pub fn with_evaluator(maximizing_color: Color, options: EngineOptions, evaluator: Box<dyn Evaluator>) -> Self {
+        Self {
+            maximizing_color,
+            options,
+            evaluator,
+            network: None,
+            tablebase: None,
+            book: None,
+            skill: None,
+            tt: TranspositionTable::new(options.tt_size),
+            rng: StdRng::from_entropy(),
+            on_info: None,
+            abort: None,
+            time: None,
+            stats: SearchStats::default(),
         }
+    }
 
-        let mut best_score = f64::MIN;
-        let mut best_plies = Vec::new();
+    pub fn options(&self) -> &EngineOptions {
+        &self.options
+    }
 
-        for ply in backend.get_legal_plies(self.maximizing_color) {
-            let mut backend_clone = backend.clone();
-            backend_clone.ply(ply.clone());
+    pub fn evaluator(&self) -> &dyn Evaluator {
+        self.evaluator.as_ref()
+    }
 
-            let score = self.alpha_beta(&backend_clone, depth - 1, f64::MIN, f64::MAX, false);
+    pub fn network(&self) -> Option<&Network> {
+        self.network.as_ref()
+    }
 
-            if score > best_score {
-                best_score = score;
-                best_plies = vec![ply];
-            } else if score == best_score {
-                best_plies.push(ply);
-            }
-        }
+    /// Sets the network used to evaluate positions. Without a network the engine
+    /// falls back to its evaluator.
+    pub fn set_network(&mut self, network: Option<Network>) {
+        self.network = network;
+        self.tt.clear();
+    }
 
-        if best_plies.is_empty() {
-            return f64::MIN;
-        }
+    pub fn tablebase(&self) -> Option<&Tablebase> {
+        self.tablebase.as_deref()
+    }
+
+    /// Sets the endgame tablebase the search probes once few enough pieces are left.
+    /// The tablebase is shared, so several engines can use it without loading it twice.
+    pub fn set_tablebase(&mut self, tablebase: Option<Arc<Tablebase>>) {
+        self.tablebase = tablebase;
+        self.tt.clear();
+    }
+
+    pub fn book(&self) -> Option<&Book> {
+        self.book.as_deref()
+    }
+
+    /// Sets the opening book consulted before searching. Like the tablebase, the book is shared.
+    pub fn set_book(&mut self, book: Option<Arc<Book>>) {
+        self.book = book;
+    }
+
+    pub fn skill(&self) -> Option<Skill> {
+        self.skill
+    }
+
+    /// Weakens the engine to the given skill level, or restores full strength with `None`.
+    pub fn set_skill(&mut self, skill: Option<Skill>) {
+        self.skill = skill;
+    }
+
+    /// Seeds the random choices of the opening book and the skill levels, which makes
+    /// games reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Replaces the evaluation used by the search.
+    /// The transposition table is cleared, since its scores came from the old evaluation.
+    pub fn set_evaluator(&mut self, evaluator: Box<dyn Evaluator>) {
+        self.evaluator = evaluator;
+        self.tt.clear();
+    }
+
+    /// Sets a callback that receives the progress of the main search thread after every
+    /// completed iteration. Use a closure sending to a channel to watch a search from
+    /// another thread.
+    pub fn set_info_callback(&mut self, on_info: Option<InfoCallback>) {
+        self.on_info = on_info;
+    }
+
+    /// Sets a flag that stops the running search once it is set, e.g. from another thread.
+    /// The search still completes its first iteration, so it always returns a move.
+    /// The flag is not reset by the engine.
+    pub fn set_abort(&mut self, abort: Option<Arc<AtomicBool>>) {
+        self.abort = abort;
+    }
+
+    /// Returns the statistics of the last search, summed over all threads.
+    pub fn stats(&self) -> &SearchStats {
+        &self.stats
+    }
+
+    /// Returns the number of nodes visited by the last search, summed over all threads.
+    pub fn nodes(&self) -> u64 {
+        self.stats.nodes
+    }
 
-        if best_plies.len() == 1 {
-            println!("Best move: {} ~ {}", best_plies[0], best_score);
+    /// Returns the number of tablebase hits of the last search, summed over all threads.
+    pub fn tb_hits(&self) -> u64 {
+        self.stats.tb_hits
+    }
+
+    /// Clears the transposition table, e.g. before starting a new game.
+    pub fn clear(&mut self) {
+        self.tt.clear();
+    }
+
+    /// Searches the position on the board with iterative deepening and returns its
+    /// score from the point of view of the maximizing color.
+    pub fn evaluate(&mut self, board: &Board) -> f64 {
+        let result = self.think(board);
+        if board.to_move() == self.maximizing_color {
+            result.score
         } else {
-            for ply in best_plies.iter() {
-                println!("Best move: {} ~ {}", ply, best_score);
-            }
+            -result.score
         }
+    }
+
+    /// Searches the position on the board with iterative deepening and returns the
+    /// result of the deepest completed iteration.
+    ///
+    /// With more than one thread, helper threads search the same position (Lazy SMP)
+    /// and only contribute through the shared transposition table.
+    ///
+    /// Positions in the opening book are not searched, the engine picks one of the book
+    /// moves at random instead. Positions in the tablebase are not searched either, the
+    /// engine plays the ply that preserves the result.
+    ///
+    /// With a limited skill level, the search is restricted and a ply is picked at random
+    /// among the best lines, see [`Skill`].
+    ///
+    /// With a fixed time per move set in the options, the search ends when the time is up.
+    pub fn think(&mut self, board: &Board) -> SearchResult {
+        self.think_within(board, self.options.movetime.map(TimeLimits::fixed).map(TimeManager::new))
+    }
 
-        self.seen_states.insert(backend.state_hash(), best_score);
-        best_score
+    /// Like [`Engine::think`], but budgets the time for the move from the clock of the
+    /// side to move, see [`TimeLimits::from_control`]. The search may end before reaching
+    /// the configured depth.
+    pub fn think_with_clock(&mut self, board: &Board, clock: &TimeControl) -> SearchResult {
+        self.think_within(board, Some(TimeManager::new(TimeLimits::from_control(clock))))
     }
 
-    fn alpha_beta(
+    /// Like [`Engine::think`] or, given a clock, [`Engine::think_with_clock`], but the time
+    /// only starts to run once `hit` is set, when the opponent played the expected reply.
+    pub(crate) fn ponder(
         &mut self,
-        backend: &Box<dyn Backend>,
-        depth: usize,
-        mut alpha: f64,
-        mut beta: f64,
-        maximizing: bool,
-    ) -> f64 {
-        if let Some(&cached_score) = self.seen_states.get(&backend.state_hash()) {
-            return cached_score;
+        board: &Board,
+        clock: Option<&TimeControl>,
+        hit: Arc<OnceLock<Instant>>,
+    ) -> SearchResult {
+        let limits = clock.map(TimeLimits::from_control).or(self.options.movetime.map(TimeLimits::fixed));
+        self.think_within(board, limits.map(|limits| TimeManager::pondering(limits, hit)))
+    }
+
+    fn think_within(&mut self, board: &Board, time: Option<TimeManager>) -> SearchResult {
+        if let Some(ply) = self.book.as_deref().and_then(|book| book.pick(board, &mut self.rng)) {
+            self.stats = SearchStats::default();
+            return SearchResult { score: 0.0, depth: 0, pv: vec![ply], nodes: 0, tb_hits: 0 };
+        }
+        if let Some(result) = self.probe_root(board) {
+            self.stats = SearchStats { tb_hits: result.tb_hits, ..Default::default() };
+            return result;
         }
-        let gamestate = backend.get_gamestate();
-        if gamestate.is_over() {
-            if gamestate.is_draw() {
-                self.seen_states.insert(backend.state_hash(), 0.0);
-                return 0.0;
+        if time.is_some() {
+            // Nothing to think about with a single reply, which is common due to forced
+            // captures. The time is better spent on the next move.
+            if let [ply] = board.get_backend().get_legal_plies(board.to_move()).as_slice() {
+                self.stats = SearchStats::default();
+                return SearchResult { score: 0.0, depth: 0, pv: vec![ply.clone()], nodes: 0, tb_hits: 0 };
+            }
+        }
+
+        self.time = time;
+        let result = self.search_best(board);
+        self.time = None;
+        result
+    }
+
+    fn search_best(&mut self, board: &Board) -> SearchResult {
+        if let Some(skill) = self.skill.filter(Skill::is_limited) {
+            let lines = self.search_lines(board, skill.lines());
+            if let Some(result) = skill.pick(lines, &mut self.rng) {
+                return result;
             }
-            let score = if Some(self.maximizing_color) == gamestate.winner() { f64::MAX } else { f64::MIN };
-            self.seen_states.insert(backend.state_hash(), score);
-            return score;
         }
 
-        if depth == 0 {
-            let score = static_eval::static_eval(backend, self.maximizing_color);
-            self.seen_states.insert(backend.state_hash(), score);
-            return score;
+        let start = Instant::now();
+        let (result, stats) = self.search_root(board, &[]);
+        self.stats = SearchStats { elapsed: start.elapsed(), ..stats };
+        result
+    }
+
+    /// Searches the best `multi_pv` lines of the position, as set in the options, and
+    /// returns them ranked best first. Fewer lines are returned if the side to move has
+    /// fewer legal plies.
+    ///
+    /// Every line is a search of its own with the first plies of the lines found before
+    /// left out at the root, so all lines get exact scores. Unlike [`Engine::think`] the
+    /// opening book and the tablebase are not consulted at the root.
+    pub fn analyze(&mut self, board: &Board) -> Vec<SearchResult> {
+        self.search_lines(board, self.options.multi_pv)
+    }
+
+    /// Searches the best `count` lines of the position and returns them ranked best first.
+    fn search_lines(&mut self, board: &Board, count: usize) -> Vec<SearchResult> {
+        let start = Instant::now();
+        let count = count.max(1).min(board.get_backend().get_legal_plies(board.to_move()).len());
+        let mut lines: Vec<SearchResult> = Vec::with_capacity(count);
+        let mut excluded = Vec::with_capacity(count);
+        let mut stats = SearchStats::default();
+        while lines.len() < count {
+            let (result, line_stats) = self.search_root(board, &excluded);
+            stats.merge(&line_stats);
+            let Some(best) = result.best_ply() else {
+                break;
+            };
+            excluded.push(best.clone());
+            lines.push(result);
         }
+        // A later line can come out ahead when the search of an earlier one was unstable.
+        lines.sort_by(|a, b| b.score.total_cmp(&a.score));
+        self.stats = SearchStats { elapsed: start.elapsed(), ..stats };
+        lines
+    }
 
-        let mut best_score = if maximizing { f64::MIN } else { f64::MAX };
+    /// Runs the iterative deepening search on all threads, leaving out the `excluded`
+    /// plies at the root.
+    fn search_root(&mut self, board: &Board, excluded: &[Ply]) -> (SearchResult, SearchStats) {
+        let color = board.to_move();
+        let stop = AtomicBool::new(false);
+        let mut on_info = self.on_info.take();
+        let options = self.skill.map_or(self.options, |skill| skill.limit(self.options));
+        let context = SearchContext { options: &options, ..self.context(&stop, excluded) };
 
-        for ply in
-            backend.get_legal_plies(if maximizing { self.maximizing_color } else { self.maximizing_color.opposite() })
-        {
-            let mut backend_clone = backend.clone();
-            backend_clone.ply(ply.clone());
+        let (result, stats) = thread::scope(|scope| {
+            let helpers: Vec<_> = (1..self.options.threads.max(1))
+                .map(|id| {
+                    let backend = board.get_backend().clone_box();
+                    scope.spawn(move || {
+                        // Odd helpers start one iteration deeper to diversify the search.
+                        let mut searcher = Searcher::new(context, true);
+                        searcher.iterate(backend.as_ref(), color, 1 + id % 2, &mut |_| {});
+                        searcher.stats
+                    })
+                })
+                .collect();
 
-            let score = self.alpha_beta(&backend_clone, depth - 1, alpha, beta, !maximizing);
+            let mut searcher = Searcher::new(context, false);
+            let mut result = searcher.iterate(board.get_backend(), color, 1, &mut |info| {
+                if let Some(on_info) = on_info.as_mut() {
+                    on_info(info);
+                }
+            });
+            stop.store(true, Ordering::Relaxed);
 
-            if maximizing {
-                best_score = best_score.max(score);
-                alpha = alpha.max(score);
-            } else {
-                best_score = best_score.min(score);
-                beta = beta.min(score);
+            let mut stats = searcher.stats;
+            for helper in helpers.into_iter().filter_map(|helper| helper.join().ok()) {
+                stats.merge(&helper);
             }
+            result.nodes = stats.nodes;
+            result.tb_hits = stats.tb_hits;
+            (result, stats)
+        });
 
-            if beta <= alpha {
-                break;
+        self.on_info = on_info;
+        (result, stats)
+    }
+
+    /// Picks the ply that preserves the tablebase result of the position: the fastest win,
+    /// any draw or the slowest loss. Returns `None` if the position is not in the tablebase.
+    fn probe_root(&self, board: &Board) -> Option<SearchResult> {
+        let tablebase = self.tablebase.as_deref()?;
+        let (backend, color) = (board.get_backend(), board.to_move());
+        let outcome = tablebase.probe(backend, color)?;
+
+        let mut best = None;
+        let mut tb_hits = 1;
+        for ply in backend.get_legal_plies(color) {
+            let mut child = backend.clone_box();
+            child.ply(ply.clone());
+            let child_outcome = tablebase.probe(child.as_ref(), color.opposite())?;
+            tb_hits += 1;
+            let rank = match child_outcome {
+                Outcome::Loss(distance) => i32::MAX - distance as i32,
+                Outcome::Draw => 0,
+                Outcome::Win(distance) => distance as i32 - i32::MAX,
+            };
+            if best.as_ref().is_none_or(|(best_rank, _)| rank > *best_rank) {
+                best = Some((rank, ply));
             }
         }
 
-        self.seen_states.insert(backend.state_hash(), best_score);
-        best_score
+        Some(SearchResult {
+            score: tablebase_score(outcome, 0),
+            depth: 0,
+            pv: best.map(|(_, ply)| ply).into_iter().collect(),
+            nodes: 0,
+            tb_hits,
+        })
+    }
+
+    /// Searches the position to a fixed depth with a full window on the calling thread
+    /// and returns the score from the point of view of `color`, the side to move.
+    pub fn search(&mut self, backend: &dyn Backend, color: Color, depth: usize) -> f64 {
+        let stop = AtomicBool::new(false);
+        let start = Instant::now();
+        let mut searcher = Searcher::new(self.context(&stop, &[]), false);
+        let score = searcher.search(backend, color, depth);
+        let stats = searcher.stats;
+        self.stats = SearchStats { elapsed: start.elapsed(), ..stats };
+        score
+    }
+
+    fn context<'a>(&'a self, stop: &'a AtomicBool, excluded: &'a [Ply]) -> SearchContext<'a> {
+        SearchContext {
+            options: &self.options,
+            evaluator: self.evaluator.as_ref(),
+            network: self.network.as_ref(),
+            tt: &self.tt,
+            tablebase: self.tablebase.as_deref(),
+            stop,
+            abort: self.abort.as_deref(),
+            time: self.time.as_ref(),
+            excluded,
+        }
     }
 }
@@ -0,0 +1,29 @@
+mod breakdown;
+mod material;
+mod params;
+mod positional;
+
+use shanks_core::board::{Backend, Color};
+
+pub use breakdown::{EvalBreakdown, EvalTerm};
+pub use material::{MaterialEvaluator, KING_VALUE, MAN_VALUE};
+pub use params::{EvalParams, TERM_COUNT, TERM_NAMES};
+pub use positional::{features, PositionalEvaluator};
+
+/// A static evaluation of positions used at the leaves of the search.
+///
+/// Evaluators are shared between all search threads and therefore have to be `Send + Sync`.
+pub trait Evaluator: Send + Sync {
+    /// Returns the score of the position from the point of view of `color`.
+    /// Positive scores are good for `color`, negative scores for its opponent.
+    fn evaluate(&self, backend: &dyn Backend, color: Color) -> f64;
+
+    /// Explains the evaluation of the position term by term.
+    ///
+    /// The default implementation only reports the total score for white.
+    fn breakdown(&self, backend: &dyn Backend) -> EvalBreakdown {
+        let mut breakdown = EvalBreakdown::default();
+        breakdown.push("total", self.evaluate(backend, Color::White), 0.0);
+        breakdown
+    }
+}
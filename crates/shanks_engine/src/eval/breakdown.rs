@@ -0,0 +1,52 @@
+use shanks_core::board::Color;
+
+/// The contribution of a single evaluation term for both sides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalTerm {
+    pub name: &'static str,
+    pub white: f64,
+    pub black: f64,
+}
+
+/// A per-term explanation of a static evaluation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvalBreakdown {
+    pub terms: Vec<EvalTerm>,
+}
+
+impl EvalBreakdown {
+    pub fn push(&mut self, name: &'static str, white: f64, black: f64) {
+        self.terms.push(EvalTerm { name, white, black });
+    }
+
+    /// Returns the total score from the point of view of `color`.
+    pub fn total(&self, color: Color) -> f64 {
+        let white: f64 = self.terms.iter().map(|term| term.white - term.black).sum();
+        match color {
+            Color::White => white,
+            Color::Black => -white,
+        }
+    }
+}
+
+impl std::fmt::Display for EvalBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{:<14}{:>9}{:>9}{:>9}", "term", "white", "black", "total")?;
+        writeln!(f, "{}", "-".repeat(41))?;
+        for term in &self.terms {
+            let (white, black) = (positive_zero(term.white), positive_zero(term.black));
+            writeln!(f, "{:<14}{:>9.3}{:>9.3}{:>9.3}", term.name, white, black, positive_zero(white - black))?;
+        }
+        writeln!(f, "{}", "-".repeat(41))?;
+        write!(f, "{:<14}{:>27.3}", "total", positive_zero(self.total(Color::White)))
+    }
+}
+
+/// Avoids printing `-0.000` for terms with a negative weight.
+fn positive_zero(value: f64) -> f64 {
+    if value == 0.0 {
+        0.0
+    } else {
+        value
+    }
+}
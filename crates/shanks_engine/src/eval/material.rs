@@ -0,0 +1,40 @@
+use shanks_core::board::{Backend, Color};
+
+use super::{EvalBreakdown, Evaluator};
+
+pub const MAN_VALUE: f64 = 1.0;
+pub const KING_VALUE: f64 = 3.0;
+
+/// The default evaluation, counting the material of both sides.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&self, backend: &dyn Backend, color: Color) -> f64 {
+        material_value(backend, color)
+    }
+
+    fn breakdown(&self, backend: &dyn Backend) -> EvalBreakdown {
+        let mut breakdown = EvalBreakdown::default();
+        breakdown.push(
+            "men",
+            backend.man_count(Color::White) as f64 * MAN_VALUE,
+            backend.man_count(Color::Black) as f64 * MAN_VALUE,
+        );
+        breakdown.push(
+            "kings",
+            backend.king_count(Color::White) as f64 * KING_VALUE,
+            backend.king_count(Color::Black) as f64 * KING_VALUE,
+        );
+        breakdown
+    }
+}
+
+pub fn material_value(backend: &dyn Backend, color: Color) -> f64 {
+    let pvalue = backend.man_count(color) as f64 * MAN_VALUE + backend.king_count(color) as f64 * KING_VALUE;
+
+    let nvalue = backend.man_count(color.opposite()) as f64 * MAN_VALUE
+        + backend.king_count(color.opposite()) as f64 * KING_VALUE;
+
+    pvalue - nvalue
+}
@@ -0,0 +1,121 @@
+use std::{fs, io, path::Path};
+
+/// Names of the evaluation terms, in the order they are stored in [`EvalParams::weights`].
+pub const TERM_NAMES: [&str; TERM_COUNT] = [
+    "man",
+    "king",
+    "back_rank",
+    "center",
+    "mobility",
+    "tempo",
+    "runaway",
+    "king_center",
+    "trapped_king",
+    "dog_hole",
+    "bridge",
+];
+pub const TERM_COUNT: usize = 11;
+
+/// The weights of the terms of the [`PositionalEvaluator`](super::PositionalEvaluator).
+///
+/// Every term is counted for both sides and the weight is applied to the difference,
+/// so a positive weight rewards a feature and a negative weight penalizes it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalParams {
+    /// Value of a man.
+    pub man: f64,
+    /// Value of a king.
+    pub king: f64,
+    /// Men still guarding the own back rank against promotions.
+    pub back_rank: f64,
+    /// Men on the eight central squares.
+    pub center: f64,
+    /// Number of legal plies.
+    pub mobility: f64,
+    /// Sum of the ranks the men have advanced.
+    pub tempo: f64,
+    /// Men no opposing piece can stop from crowning.
+    pub runaway: f64,
+    /// Closeness of the kings to the center of the board.
+    pub king_center: f64,
+    /// Kings that can neither move nor capture.
+    pub trapped_king: f64,
+    /// A man stuck in the dog-hole next to the opponent's double corner.
+    pub dog_hole: f64,
+    /// Both bridge squares of the back rank held by men.
+    pub bridge: f64,
+}
+
+impl EvalParams {
+    /// Returns the weights in the order of [`TERM_NAMES`].
+    pub fn weights(&self) -> [f64; TERM_COUNT] {
+        [
+            self.man,
+            self.king,
+            self.back_rank,
+            self.center,
+            self.mobility,
+            self.tempo,
+            self.runaway,
+            self.king_center,
+            self.trapped_king,
+            self.dog_hole,
+            self.bridge,
+        ]
+    }
+
+    /// Creates the parameters from weights in the order of [`TERM_NAMES`].
+    pub fn from_weights(weights: [f64; TERM_COUNT]) -> Self {
+        let [man, king, back_rank, center, mobility, tempo, runaway, king_center, trapped_king, dog_hole, bridge] =
+            weights;
+        Self { man, king, back_rank, center, mobility, tempo, runaway, king_center, trapped_king, dog_hole, bridge }
+    }
+
+    /// Loads parameters from a file with one `name = weight` line per term.
+    ///
+    /// Terms missing from the file keep their default weight, empty lines and
+    /// lines starting with `#` are ignored.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut weights = Self::default().weights();
+        for line in fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+            let (name, weight) =
+                line.split_once('=').ok_or_else(|| invalid(format!("expected name = weight: {}", line)))?;
+            let term = TERM_NAMES
+                .iter()
+                .position(|term| *term == name.trim())
+                .ok_or_else(|| invalid(format!("unknown term: {}", name.trim())))?;
+            weights[term] = weight.trim().parse().map_err(|_| invalid(format!("invalid weight: {}", weight.trim())))?;
+        }
+        Ok(Self::from_weights(weights))
+    }
+
+    /// Saves the parameters in the format read by [`EvalParams::load`].
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let lines: Vec<String> =
+            TERM_NAMES.iter().zip(self.weights()).map(|(name, weight)| format!("{} = {}", name, weight)).collect();
+        fs::write(path, lines.join("\n") + "\n")
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self {
+            man: 1.0,
+            king: 1.4,
+            back_rank: 0.1,
+            center: 0.05,
+            mobility: 0.02,
+            tempo: 0.01,
+            runaway: 0.3,
+            king_center: 0.05,
+            trapped_king: -0.3,
+            dog_hole: -0.1,
+            bridge: 0.1,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use shanks_core::board::{Backend, Color, Piece, Square};
+
+use super::{EvalBreakdown, EvalParams, Evaluator, TERM_COUNT, TERM_NAMES};
+
+// Indices of the terms in the feature arrays.
+const MAN: usize = 0;
+const KING: usize = 1;
+const BACK_RANK: usize = 2;
+const CENTER: usize = 3;
+const MOBILITY: usize = 4;
+const TEMPO: usize = 5;
+const RUNAWAY: usize = 6;
+const KING_CENTER: usize = 7;
+const TRAPPED_KING: usize = 8;
+const DOG_HOLE: usize = 9;
+const BRIDGE: usize = 10;
+
+/// An evaluation made up of well-known checkers terms, weighted by [`EvalParams`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PositionalEvaluator {
+    pub params: EvalParams,
+}
+
+impl PositionalEvaluator {
+    pub fn new(params: EvalParams) -> Self {
+        Self { params }
+    }
+}
+
+impl Evaluator for PositionalEvaluator {
+    fn evaluate(&self, backend: &dyn Backend, color: Color) -> f64 {
+        let own = features(backend, color);
+        let opponent = features(backend, color.opposite());
+        self.params.weights().iter().zip(own.iter().zip(opponent.iter())).map(|(w, (o, p))| w * (o - p)).sum()
+    }
+
+    fn breakdown(&self, backend: &dyn Backend) -> EvalBreakdown {
+        let white = features(backend, Color::White);
+        let black = features(backend, Color::Black);
+        let mut breakdown = EvalBreakdown::default();
+        for (term, weight) in self.params.weights().iter().enumerate() {
+            breakdown.push(TERM_NAMES[term], weight * white[term], weight * black[term]);
+        }
+        breakdown
+    }
+}
+
+/// Returns the unweighted value of every term for the pieces of `color`,
+/// in the order of [`TERM_NAMES`](super::TERM_NAMES).
+pub fn features(backend: &dyn Backend, color: Color) -> [f64; TERM_COUNT] {
+    let view = View::new(backend, color);
+    let opponent_has_kings = Square::ALL.iter().any(|&square| view.opponent(square).is_some_and(|p| p.is_king()));
+
+    let mut features = [0.0; TERM_COUNT];
+    for square in Square::ALL {
+        let Some(piece) = view.own(square) else {
+            continue;
+        };
+        let (file, rank) = (square.file() as i8, square.rank() as i8);
+
+        if piece.is_king() {
+            features[KING] += 1.0;
+            features[KING_CENTER] += 3.5 - ((file as f64 - 3.5).abs() + (rank as f64 - 3.5).abs()) / 2.0;
+            if view.is_trapped(square) {
+                features[TRAPPED_KING] += 1.0;
+            }
+            continue;
+        }
+
+        features[MAN] += 1.0;
+        if rank == 0 {
+            features[BACK_RANK] += 1.0;
+        }
+        if (2..=5).contains(&file) && (2..=5).contains(&rank) {
+            features[CENTER] += 1.0;
+        }
+        features[TEMPO] += rank as f64;
+        if !opponent_has_kings && view.is_runaway(square) {
+            features[RUNAWAY] += 1.0;
+        }
+    }
+
+    features[MOBILITY] = backend.get_legal_plies(color).len() as f64;
+
+    // Dog-hole: a man on a7, blocked by the opponent on b8.
+    if view.own(Square::A7).is_some_and(|p| !p.is_king()) && view.opponent(Square::B8).is_some() {
+        features[DOG_HOLE] = 1.0;
+    }
+    // Bridge: men on c1 and g1 keep the opponent from crowning.
+    if view.own(Square::C1).is_some_and(|p| !p.is_king()) && view.own(Square::G1).is_some_and(|p| !p.is_king()) {
+        features[BRIDGE] = 1.0;
+    }
+
+    features
+}
+
+/// The board as seen by one side: its men always move towards rank 8.
+struct View<'a> {
+    backend: &'a dyn Backend,
+    color: Color,
+}
+
+impl<'a> View<'a> {
+    fn new(backend: &'a dyn Backend, color: Color) -> Self {
+        Self { backend, color }
+    }
+
+    fn get(&self, square: Square) -> Option<Piece> {
+        match self.color {
+            Color::White => self.backend.get_piece(square),
+            Color::Black => self.backend.get_piece(square.flipped()),
+        }
+    }
+
+    fn own(&self, square: Square) -> Option<Piece> {
+        self.get(square).filter(|piece| piece.color() == self.color)
+    }
+
+    fn opponent(&self, square: Square) -> Option<Piece> {
+        self.get(square).filter(|piece| piece.color() != self.color)
+    }
+
+    /// A man is a runaway if no opposing piece stands in the cone in front of it.
+    fn is_runaway(&self, square: Square) -> bool {
+        Square::ALL.iter().all(|&other| {
+            let ahead = other.rank() as i8 - square.rank() as i8;
+            let aside = (other.file() as i8 - square.file() as i8).abs();
+            ahead <= 0 || aside > ahead || self.opponent(other).is_none()
+        })
+    }
+
+    /// A king is trapped if every diagonal is blocked and it can't capture.
+    fn is_trapped(&self, square: Square) -> bool {
+        [(-1, -1), (-1, 1), (1, -1), (1, 1)].iter().all(|&(dx, dy)| {
+            let Some(target) = square.moved_by(dx, dy) else {
+                return true;
+            };
+            if self.get(target).is_none() {
+                return false;
+            }
+            if self.own(target).is_some() {
+                return true;
+            }
+            target.moved_by(dx, dy).is_none_or(|landing| self.get(landing).is_some())
+        })
+    }
+}
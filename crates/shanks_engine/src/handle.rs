@@ -0,0 +1,194 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, OnceLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use shanks_core::board::{Board, Ply};
+
+use crate::{Engine, SearchInfo, SearchResult, SearchStats, TimeControl};
+
+/// An event sent by an [`EngineHandle`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchEvent {
+    /// The progress of the search after a completed iteration.
+    Info(SearchInfo),
+    /// The search is over. `ponder` is the expected reply to the best ply, the position
+    /// to ponder on while the opponent thinks.
+    BestMove { result: SearchResult, ponder: Option<Ply>, stats: SearchStats },
+}
+
+enum Command {
+    /// `hit` is set for ponder searches and holds the time of the ponder hit once it comes.
+    Go {
+        board: Board,
+        clock: Option<TimeControl>,
+        hit: Option<Arc<OnceLock<Instant>>>,
+        abort: Arc<AtomicBool>,
+    },
+    PonderHit,
+    Stop,
+    Quit,
+}
+
+/// Runs an [`Engine`] on a thread of its own, so searches do not block the caller.
+///
+/// Searches are started with [`EngineHandle::go`] and report their progress and result
+/// as [`SearchEvent`]s. [`EngineHandle::ponder`] searches the position after the expected
+/// reply while the opponent thinks: if the opponent plays it, [`EngineHandle::ponder_hit`]
+/// turns the ponder search into the real one, otherwise [`EngineHandle::stop`] ends it.
+/// Either way the transposition table keeps what the ponder search found. The time of a
+/// ponder search only starts to run on the ponder hit.
+pub struct EngineHandle {
+    commands: Sender<Command>,
+    events: Receiver<SearchEvent>,
+    /// The abort flag of the latest search.
+    abort: Arc<AtomicBool>,
+    /// The time of the ponder hit of the latest search.
+    hit: Arc<OnceLock<Instant>>,
+    thread: Option<JoinHandle<Engine>>,
+}
+
+impl EngineHandle {
+    pub fn new(engine: Engine) -> Self {
+        let (commands, command_receiver) = mpsc::channel();
+        let (event_sender, events) = mpsc::channel();
+        let thread = thread::spawn(move || run(engine, command_receiver, event_sender));
+        Self {
+            commands,
+            events,
+            abort: Arc::new(AtomicBool::new(false)),
+            hit: Arc::new(OnceLock::new()),
+            thread: Some(thread),
+        }
+    }
+
+    /// Starts searching the position. A search still running is stopped first.
+    pub fn go(&mut self, board: &Board) {
+        self.start(board, None, false);
+    }
+
+    /// Starts searching the position with the time for the move budgeted from the clock
+    /// of the side to move, see [`Engine::think_with_clock`].
+    pub fn go_with_clock(&mut self, board: &Board, clock: &TimeControl) {
+        self.start(board, Some(*clock), false);
+    }
+
+    /// Starts searching the position after the expected reply of the opponent. The result
+    /// is held back until [`EngineHandle::ponder_hit`] or [`EngineHandle::stop`] is called,
+    /// and dropped if another search is started instead. A fixed time per move set in the options only starts to run on the ponder hit.
+    pub fn ponder(&mut self, board: &Board) {
+        self.start(board, None, true);
+    }
+
+    /// Like [`EngineHandle::ponder`], but once the ponder hit comes, the search takes the
+    /// time budgeted from the clock of the side to move, see [`Engine::think_with_clock`].
+    pub fn ponder_with_clock(&mut self, board: &Board, clock: &TimeControl) {
+        self.start(board, Some(*clock), true);
+    }
+
+    /// The opponent played the expected reply: the ponder search becomes the real search,
+    /// starts its clock and reports its result as soon as it is done.
+    pub fn ponder_hit(&self) {
+        let _ = self.hit.set(Instant::now());
+        let _ = self.commands.send(Command::PonderHit);
+    }
+
+    /// Stops the running search, which reports the result of its last completed iteration.
+    pub fn stop(&self) {
+        self.abort.store(true, Ordering::Relaxed);
+        let _ = self.commands.send(Command::Stop);
+    }
+
+    /// Returns the receiver of the events of all searches.
+    pub fn events(&self) -> &Receiver<SearchEvent> {
+        &self.events
+    }
+
+    /// Waits for the result of the running search and the expected reply, skipping the
+    /// info events. Returns `None` if no result arrives within `timeout`.
+    pub fn wait(&self, timeout: Duration) -> Option<(SearchResult, Option<Ply>)> {
+        loop {
+            match self.events.recv_timeout(timeout) {
+                Ok(SearchEvent::BestMove { result, ponder, .. }) => return Some((result, ponder)),
+                Ok(SearchEvent::Info(_)) => {}
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    /// Stops the search thread and returns the engine.
+    pub fn into_engine(mut self) -> Engine {
+        self.shut_down().expect("the engine thread has not been joined before")
+    }
+
+    fn start(&mut self, board: &Board, clock: Option<TimeControl>, ponder: bool) {
+        // Every search gets its own flag, so stopping one search cannot affect the next.
+        self.abort.store(true, Ordering::Relaxed);
+        self.abort = Arc::new(AtomicBool::new(false));
+        self.hit = Arc::new(OnceLock::new());
+        let hit = ponder.then(|| self.hit.clone());
+        let _ = self.commands.send(Command::Go { board: board.clone(), clock, hit, abort: self.abort.clone() });
+    }
+
+    fn shut_down(&mut self) -> Option<Engine> {
+        self.abort.store(true, Ordering::Relaxed);
+        let _ = self.commands.send(Command::Quit);
+        self.thread.take()?.join().ok()
+    }
+}
+
+impl Drop for EngineHandle {
+    fn drop(&mut self) {
+        self.shut_down();
+    }
+}
+
+fn run(mut engine: Engine, commands: Receiver<Command>, events: Sender<SearchEvent>) -> Engine {
+    let info_events = events.clone();
+    engine.set_info_callback(Some(Box::new(move |info| {
+        let _ = info_events.send(SearchEvent::Info(info.clone()));
+    })));
+
+    let mut pending = None;
+    loop {
+        let Some(command) = pending.take().or_else(|| commands.recv().ok()) else {
+            break;
+        };
+        match command {
+            Command::Go { board, clock, hit, abort } => {
+                engine.set_abort(Some(abort));
+                let pondering = hit.is_some();
+                let result = match (hit, clock) {
+                    (Some(hit), clock) => engine.ponder(&board, clock.as_ref(), hit),
+                    (None, Some(clock)) => engine.think_with_clock(&board, &clock),
+                    (None, None) => engine.think(&board),
+                };
+                engine.set_abort(None);
+                if pondering {
+                    // Hold the result back until the opponent has moved.
+                    match commands.recv() {
+                        Ok(Command::PonderHit | Command::Stop) => {}
+                        // A new search replaces the ponder search, whose result is of no use.
+                        Ok(command @ Command::Go { .. }) => {
+                            pending = Some(command);
+                            continue;
+                        }
+                        Ok(Command::Quit) | Err(_) => break,
+                    }
+                }
+                let ponder = result.pv.get(1).cloned();
+                let _ = events.send(SearchEvent::BestMove { result, ponder, stats: *engine.stats() });
+            }
+            Command::PonderHit | Command::Stop => {}
+            Command::Quit => break,
+        }
+    }
+
+    engine.set_info_callback(None);
+    engine
+}
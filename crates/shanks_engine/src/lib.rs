@@ -1,4 +1,48 @@
+mod baseline;
+mod book;
+mod datagen;
 mod engine;
-mod static_eval;
+mod eval;
+mod handle;
+mod matches;
+mod mcts;
+mod nnue;
+mod options;
+mod player;
+mod search;
+mod skill;
+mod solver;
+mod stats;
+mod suite;
+mod tablebase;
+mod time;
+mod tt;
+mod tune;
+mod zero;
 
+pub use baseline::{GreedyPlayer, MinimaxPlayer, RandomPlayer};
+pub use book::{Book, BookMove};
+pub use datagen::{generate, DatagenConfig, DatagenStats, TrainingPosition};
 pub use engine::Engine;
+pub use eval::{
+    features, EvalBreakdown, EvalParams, EvalTerm, Evaluator, MaterialEvaluator, PositionalEvaluator, KING_VALUE,
+    MAN_VALUE, TERM_COUNT, TERM_NAMES,
+};
+pub use handle::{EngineHandle, SearchEvent};
+pub use matches::{play_match, MatchConfig, MatchGame, MatchStats};
+pub use mcts::{Mcts, MctsOptions, Playout};
+pub use nnue::{Accumulator, Network};
+pub use options::{EngineOptions, DEFAULT_DEPTH};
+pub use player::Player;
+pub use search::{quiescence, SearchResult, WIN_SCORE};
+pub use skill::{calibrate, Skill, MAX_SKILL, REFERENCE_ELO};
+pub use solver::{Proof, SolveResult, Solver, SolverOptions};
+pub use stats::{InfoCallback, SearchInfo, SearchStats};
+pub use suite::{parse_suite, regression_suite, run_suite, SuiteError, SuiteResult, TestPosition};
+pub use tablebase::{Material, Outcome, TableSlice, Tablebase};
+pub use time::{TimeControl, TimeLimits};
+pub use tune::Tuner;
+pub use zero::{
+    read_samples, self_play, write_samples, PolicyValueNet, SelfPlayConfig, SelfPlayStats, ZeroLoss, ZeroOptions,
+    ZeroPlayer, ZeroSample, ZeroTrainer, PLANES, POLICY_SIZE,
+};
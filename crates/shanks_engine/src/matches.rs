@@ -0,0 +1,148 @@
+use std::time::Instant;
+
+use shanks_core::board::{Ballot, Color};
+
+use crate::{Player, TimeControl};
+
+/// Settings of a match between two engines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchConfig {
+    /// Number of ballots to play, starting at `first_ballot`. Every ballot is played twice,
+    /// once with each engine as white. Wraps around after the last ballot.
+    pub ballots: usize,
+    /// Number of the first ballot, starting at 1.
+    pub first_ballot: usize,
+    /// Games still running after this many plies are adjudicated as draws.
+    pub max_plies: usize,
+    /// The clock of each engine at the start of a game. Without a clock the engines search
+    /// to their configured depth, with a clock an engine whose time runs out loses.
+    pub clock: Option<TimeControl>,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self { ballots: Ballot::all().len(), first_ballot: 1, max_plies: 300, clock: None }
+    }
+}
+
+/// A finished game of a match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchGame {
+    pub ballot: &'static Ballot,
+    /// The color played by the first engine.
+    pub first_color: Color,
+    /// The result from white's point of view: 1 for a win, 0.5 for a draw, 0 for a loss.
+    pub result: f64,
+    /// Number of plies played after the ballot.
+    pub plies: usize,
+    /// Set if the game was lost on time.
+    pub time_forfeit: bool,
+}
+
+impl MatchGame {
+    /// Returns the result from the first engine's point of view.
+    pub fn first_result(&self) -> f64 {
+        match self.first_color {
+            Color::White => self.result,
+            Color::Black => 1.0 - self.result,
+        }
+    }
+}
+
+/// Totals of a match from the first engine's point of view.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MatchStats {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl MatchStats {
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Returns the score of the first engine between 0 and 1, counting draws as half a point.
+    pub fn score(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64
+    }
+}
+
+/// Plays a match between two players on three-move ballots and hands every finished game
+/// to `on_game`. Both players play each ballot once as white and once as black, so an
+/// unbalanced opening favours neither of them.
+pub fn play_match<F>(
+    first: &mut dyn Player,
+    second: &mut dyn Player,
+    config: &MatchConfig,
+    mut on_game: F,
+) -> MatchStats
+where
+    F: FnMut(&MatchGame),
+{
+    let ballots = Ballot::all();
+    let mut stats = MatchStats::default();
+    for offset in 0..config.ballots {
+        let ballot = &ballots[(config.first_ballot.max(1) - 1 + offset) % ballots.len()];
+        for first_color in [Color::White, Color::Black] {
+            let game = play_game(first, second, ballot, first_color, config);
+            let result = game.first_result();
+            if result == 1.0 {
+                stats.wins += 1;
+            } else if result == 0.0 {
+                stats.losses += 1;
+            } else {
+                stats.draws += 1;
+            }
+            on_game(&game);
+        }
+    }
+    stats
+}
+
+fn play_game(
+    first: &mut dyn Player,
+    second: &mut dyn Player,
+    ballot: &'static Ballot,
+    first_color: Color,
+    config: &MatchConfig,
+) -> MatchGame {
+    let mut board = ballot.board().expect("the shipped ballots are legal");
+    first.new_game();
+    second.new_game();
+
+    // The clocks of white and black.
+    let mut clocks = [config.clock; 2];
+    let mut game = MatchGame { ballot, first_color, result: 0.5, plies: 0, time_forfeit: false };
+    while game.plies < config.max_plies {
+        let color = board.to_move();
+        let player: &mut dyn Player = if color == first_color { &mut *first } else { &mut *second };
+        let clock = &mut clocks[usize::from(color == Color::Black)];
+        let start = Instant::now();
+        let result = match clock {
+            Some(clock) => player.think_with_clock(&board, clock),
+            None => player.think(&board),
+        };
+        if let Some(clock) = clock {
+            let Some(remaining) = clock.remaining.checked_sub(start.elapsed()) else {
+                game.result = if color == Color::White { 0.0 } else { 1.0 };
+                game.time_forfeit = true;
+                break;
+            };
+            clock.remaining = remaining + clock.increment;
+            clock.moves_to_go = clock.moves_to_go.map(|moves| moves.saturating_sub(1).max(1));
+        }
+        match result.best_ply() {
+            Some(ply) => board.ply(ply.clone()),
+            None => {
+                game.result = if color == Color::White { 0.0 } else { 1.0 };
+                break;
+            }
+        }
+        game.plies += 1;
+    }
+    game
+}
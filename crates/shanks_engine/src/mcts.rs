@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use shanks_core::board::{Backend, Board, Color, Ply};
+
+use crate::{
+    eval::{Evaluator, MaterialEvaluator, MAN_VALUE},
+    player::Player,
+    search::quiescence,
+    time::{TimeControl, TimeLimits, TimeManager},
+    SearchResult,
+};
+
+/// The clock is read once every this many playouts.
+const TIME_CHECK_INTERVAL: u64 = 64;
+/// Win rates are kept this far from 0 and 1 when they are turned into scores.
+const MAX_CERTAINTY: f64 = 0.999;
+
+/// How the value of a new leaf of the tree is estimated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Playout {
+    /// Play random plies until the game ends.
+    Random,
+    /// Evaluate the leaf once its captures are resolved, mapping the score to a winning
+    /// chance with a logistic curve: a man ahead wins about 73% of the time.
+    Evaluation,
+}
+
+/// Settings of the [`Mcts`] engine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MctsOptions {
+    /// Number of playouts per move.
+    pub iterations: u64,
+    /// The exploration constant of UCT. Larger values try less visited plies more often.
+    pub exploration: f64,
+    pub playout: Playout,
+    /// Random playouts still running after this many plies count as draws.
+    pub max_playout_plies: usize,
+    /// Fixed time per move, which ends the search before all iterations are done.
+    pub movetime: Option<Duration>,
+}
+
+impl Default for MctsOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 10_000,
+            exploration: std::f64::consts::SQRT_2,
+            playout: Playout::Random,
+            max_playout_plies: 200,
+            movetime: None,
+        }
+    }
+}
+
+/// A node of the search tree, reached by `ply` from its parent.
+struct Node {
+    ply: Option<Ply>,
+    parent: usize,
+    children: Vec<usize>,
+    /// Legal plies not expanded into children yet.
+    untried: Vec<Ply>,
+    visits: u32,
+    /// Sum of the playout results for the side that played `ply`.
+    wins: f64,
+}
+
+impl Node {
+    fn new(ply: Option<Ply>, parent: usize, untried: Vec<Ply>) -> Self {
+        Self { ply, parent, children: Vec::new(), untried, visits: 0, wins: 0.0 }
+    }
+}
+
+/// A Monte Carlo tree search engine using UCT, as an alternative to the alpha-beta
+/// [`Engine`](crate::Engine).
+///
+/// Every iteration walks down the tree choosing the child with the best upper confidence
+/// bound, expands one untried ply, estimates the new leaf by a [`Playout`] and adds the
+/// result to all nodes on the way back up. The most visited ply is played. The tree is
+/// rebuilt for every move.
+pub struct Mcts {
+    options: MctsOptions,
+    evaluator: Box<dyn Evaluator>,
+    rng: StdRng,
+    nodes: Vec<Node>,
+}
+
+impl Mcts {
+    pub fn new(options: MctsOptions) -> Self {
+        Self::with_evaluator(options, Box::new(MaterialEvaluator))
+    }
+
+    /// Creates an engine with the evaluator used by [`Playout::Evaluation`].
+    pub fn with_evaluator(options: MctsOptions, evaluator: Box<dyn Evaluator>) -> Self {
+        Self { options, evaluator, rng: StdRng::from_entropy(), nodes: Vec::new() }
+    }
+
+    pub fn options(&self) -> &MctsOptions {
+        &self.options
+    }
+
+    /// Seeds the random choices of the search, which makes it reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Searches the position until all iterations are done or the time is up.
+    ///
+    /// The score of the result is the winning chance of the chosen ply mapped back to
+    /// evaluation units, the inverse of [`Playout::Evaluation`]. The principal variation
+    /// follows the most visited plies, `nodes` counts the playouts.
+    pub fn search(&mut self, board: &Board, limits: Option<TimeLimits>) -> SearchResult {
+        let (root, color) = (board.get_backend(), board.to_move());
+        let time = limits.map(TimeManager::new);
+        self.nodes.clear();
+        self.nodes.push(Node::new(None, 0, root.get_legal_plies(color)));
+
+        let mut iterations = 0;
+        while iterations < self.options.iterations {
+            if iterations % TIME_CHECK_INTERVAL == 0 && time.as_ref().is_some_and(TimeManager::past_soft_limit) {
+                break;
+            }
+            self.iterate(root, color);
+            iterations += 1;
+        }
+
+        let mut pv = Vec::new();
+        let mut node = 0;
+        while let Some(&child) = self.nodes[node].children.iter().max_by_key(|&&child| self.nodes[child].visits) {
+            pv.extend(self.nodes[child].ply.clone());
+            node = child;
+        }
+        let score = match self.nodes[0].children.iter().max_by_key(|&&child| self.nodes[child].visits) {
+            Some(&best) => {
+                let rate = self.nodes[best].wins / self.nodes[best].visits as f64;
+                let rate = rate.clamp(1.0 - MAX_CERTAINTY, MAX_CERTAINTY);
+                MAN_VALUE * (rate / (1.0 - rate)).ln()
+            }
+            None => 0.0,
+        };
+        SearchResult { score, depth: pv.len(), pv, nodes: iterations, tb_hits: 0 }
+    }
+
+    /// Runs one selection, expansion, playout and backpropagation.
+    fn iterate(&mut self, root: &dyn Backend, mut color: Color) {
+        let mut backend = root.clone_box();
+        let mut node = 0;
+        while self.nodes[node].untried.is_empty() && !self.nodes[node].children.is_empty() {
+            node = self.select(node);
+            backend.ply(self.nodes[node].ply.clone().expect("only the root has no ply"));
+            color = color.opposite();
+        }
+
+        if !self.nodes[node].untried.is_empty() {
+            let index = self.rng.gen_range(0..self.nodes[node].untried.len());
+            let ply = self.nodes[node].untried.swap_remove(index);
+            backend.ply(ply.clone());
+            color = color.opposite();
+            let child = self.nodes.len();
+            self.nodes.push(Node::new(Some(ply), node, backend.get_legal_plies(color)));
+            self.nodes[node].children.push(child);
+            node = child;
+        }
+
+        // The playout scores the leaf for the side to move, the node for the side that moved.
+        let mut result = 1.0 - self.playout(backend, color);
+        loop {
+            self.nodes[node].visits += 1;
+            self.nodes[node].wins += result;
+            if node == 0 {
+                break;
+            }
+            node = self.nodes[node].parent;
+            result = 1.0 - result;
+        }
+    }
+
+    /// Returns the child with the highest upper confidence bound.
+    fn select(&self, node: usize) -> usize {
+        let log_visits = (self.nodes[node].visits as f64).ln();
+        let bound = |child: usize| {
+            let child = &self.nodes[child];
+            let visits = child.visits as f64;
+            child.wins / visits + self.options.exploration * (log_visits / visits).sqrt()
+        };
+        let children = &self.nodes[node].children;
+        children.iter().copied().max_by(|&a, &b| bound(a).total_cmp(&bound(b))).expect("the node has children")
+    }
+
+    /// Estimates the chance of `color`, the side to move, to win from the position:
+    /// 1 for a win, 0.5 for a draw and 0 for a loss.
+    fn playout(&mut self, mut backend: Box<dyn Backend>, color: Color) -> f64 {
+        if self.options.playout == Playout::Evaluation {
+            let score = quiescence(self.evaluator.as_ref(), backend.as_ref(), color);
+            return 1.0 / (1.0 + (-score / MAN_VALUE).exp());
+        }
+
+        let mut to_move = color;
+        for _ in 0..self.options.max_playout_plies {
+            let plies = backend.get_legal_plies(to_move);
+            let Some(ply) = plies.choose(&mut self.rng) else {
+                return if to_move == color { 0.0 } else { 1.0 };
+            };
+            backend.ply(ply.clone());
+            to_move = to_move.opposite();
+        }
+        0.5
+    }
+}
+
+impl Player for Mcts {
+    fn name(&self) -> String {
+        let playout = match self.options.playout {
+            Playout::Random => "random playouts",
+            Playout::Evaluation => "evaluations",
+        };
+        format!("MCTS {} {}", self.options.iterations, playout)
+    }
+
+    fn think(&mut self, board: &Board) -> SearchResult {
+        self.search(board, self.options.movetime.map(TimeLimits::fixed))
+    }
+
+    fn think_with_clock(&mut self, board: &Board, clock: &TimeControl) -> SearchResult {
+        self.search(board, Some(TimeLimits::from_control(clock)))
+    }
+}
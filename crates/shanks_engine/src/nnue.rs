@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use shanks_core::board::{Backend, Color, Piece, Ply, Square};
+
+use crate::eval::Evaluator;
+
+pub const INPUTS: usize = 128;
+/// Upper bound of the clipped ReLU applied to the accumulators.
+pub const ACTIVATION_SCALE: i32 = 127;
+/// Scale of the output weights.
+pub const OUTPUT_SCALE: i32 = 64;
+
+const MAGIC: &[u8; 4] = b"SHNN";
+const VERSION: u32 = 1;
+/// Largest accumulator size a network file may declare, which bounds the memory `read` allocates.
+const MAX_HIDDEN: usize = 4096;
+
+/// The first layer outputs of both perspectives for one position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accumulator {
+    values: [Vec<i16>; 2],
+}
+
+impl Accumulator {
+    fn perspective(&self, color: Color) -> &[i16] {
+        &self.values[color as usize]
+    }
+}
+
+/// An efficiently updatable neural network (NNUE) evaluation.
+///
+/// The network sees the board from the perspective of both sides. Each perspective has
+/// 128 binary inputs, one per combination of owner (own or opponent), kind (man or king)
+/// and playable square, where the squares of black's perspective are rotated so that its
+/// men move up the board as well. The first layer turns each perspective into an
+/// accumulator of `hidden` `i16` values. Only a handful of inputs change with every ply,
+/// so the search updates the accumulators incrementally instead of recomputing them.
+///
+/// The accumulators of the side to move and its opponent are clipped to `0..=127`,
+/// concatenated and fed into a single `i8` output neuron, whose result is divided by
+/// `ACTIVATION_SCALE * OUTPUT_SCALE` to get a score in units of men. The accumulators
+/// wrap around on overflow, so extreme weights give odd scores but never a panic.
+///
+/// # File format
+///
+/// All values are little-endian.
+///
+/// | Field          | Type                     |
+/// |----------------|--------------------------|
+/// | magic          | `b"SHNN"`                |
+/// | version        | `u32`, currently 1       |
+/// | hidden         | `u32`, at most 4096      |
+/// | input weights  | `i16` x `128 * hidden`, grouped by input |
+/// | input biases   | `i16` x `hidden`         |
+/// | output weights | `i8` x `2 * hidden`, side to move first |
+/// | output bias    | `i32`                    |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    hidden: usize,
+    input_weights: Vec<i16>,
+    input_biases: Vec<i16>,
+    output_weights: Vec<i8>,
+    output_bias: i32,
+}
+
+impl Network {
+    /// Creates a network with small pseudo-random weights, mainly useful as a
+    /// starting point for training and for testing.
+    pub fn random(hidden: usize, seed: u64) -> Self {
+        let mut state = seed | 1;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        Self {
+            hidden,
+            input_weights: (0..INPUTS * hidden).map(|_| (next() % 65) as i16 - 32).collect(),
+            input_biases: (0..hidden).map(|_| (next() % 33) as i16).collect(),
+            output_weights: (0..2 * hidden).map(|_| (next() % 65) as i8 - 32).collect(),
+            output_bias: 0,
+        }
+    }
+
+    /// Loads a network from a file in the format described in the type documentation.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(&mut fs::File::open(path)?)
+    }
+
+    pub fn read(reader: &mut impl Read) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a shanks network file"));
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unsupported version {}", version)));
+        }
+        let hidden = read_u32(reader)? as usize;
+        if hidden > MAX_HIDDEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("too many hidden units: {}", hidden)));
+        }
+
+        let input_weights = read_i16s(reader, INPUTS * hidden)?;
+        let input_biases = read_i16s(reader, hidden)?;
+        let mut output_weights = vec![0u8; 2 * hidden];
+        reader.read_exact(&mut output_weights)?;
+        let output_bias = read_u32(reader)? as i32;
+
+        Ok(Self {
+            hidden,
+            input_weights,
+            input_biases,
+            output_weights: output_weights.into_iter().map(|weight| weight as i8).collect(),
+            output_bias,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.hidden as u32).to_le_bytes());
+        for value in self.input_weights.iter().chain(&self.input_biases) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend(self.output_weights.iter().map(|&weight| weight as u8));
+        bytes.extend_from_slice(&self.output_bias.to_le_bytes());
+        fs::write(path, bytes)
+    }
+
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// Computes the accumulator of a position from scratch.
+    pub fn refresh(&self, backend: &dyn Backend) -> Accumulator {
+        let mut accumulator = Accumulator { values: [self.input_biases.clone(), self.input_biases.clone()] };
+        for square in Square::ALL {
+            if let Some(piece) = backend.get_piece(square) {
+                self.add(&mut accumulator, square, piece);
+            }
+        }
+        accumulator
+    }
+
+    /// Writes the accumulator of the position after `ply` into `target`, given the
+    /// accumulator of the position before it. `backend` is the position before the ply,
+    /// which is needed to know the kinds of the moved and captured pieces.
+    pub fn update(&self, source: &Accumulator, target: &mut Accumulator, backend: &dyn Backend, ply: &Ply) {
+        for (target, source) in target.values.iter_mut().zip(&source.values) {
+            target.clear();
+            target.extend_from_slice(source);
+        }
+
+        if let Some(moved) = backend.get_piece(ply.from()) {
+            self.remove(target, ply.from(), moved);
+        }
+        for &square in ply.captures() {
+            if let Some(captured) = backend.get_piece(square) {
+                self.remove(target, square, captured);
+            }
+        }
+        self.add(target, ply.to(), ply.piece());
+    }
+
+    /// Returns the score of the position from the point of view of `color`, the side to move.
+    pub fn evaluate_accumulator(&self, accumulator: &Accumulator, color: Color) -> f64 {
+        let inputs = accumulator.perspective(color).iter().chain(accumulator.perspective(color.opposite()));
+        let output: i64 = inputs
+            .zip(&self.output_weights)
+            .map(|(&value, &weight)| (value as i64).clamp(0, ACTIVATION_SCALE as i64) * weight as i64)
+            .sum::<i64>()
+            + self.output_bias as i64;
+        output as f64 / (ACTIVATION_SCALE * OUTPUT_SCALE) as f64
+    }
+
+    fn add(&self, accumulator: &mut Accumulator, square: Square, piece: Piece) {
+        for color in [Color::White, Color::Black] {
+            let weights = self.weights(feature(color, square, piece));
+            for (value, weight) in accumulator.values[color as usize].iter_mut().zip(weights) {
+                *value = value.wrapping_add(*weight);
+            }
+        }
+    }
+
+    fn remove(&self, accumulator: &mut Accumulator, square: Square, piece: Piece) {
+        for color in [Color::White, Color::Black] {
+            let weights = self.weights(feature(color, square, piece));
+            for (value, weight) in accumulator.values[color as usize].iter_mut().zip(weights) {
+                *value = value.wrapping_sub(*weight);
+            }
+        }
+    }
+
+    fn weights(&self, feature: usize) -> &[i16] {
+        &self.input_weights[feature * self.hidden..(feature + 1) * self.hidden]
+    }
+}
+
+impl Evaluator for Network {
+    fn evaluate(&self, backend: &dyn Backend, color: Color) -> f64 {
+        self.evaluate_accumulator(&self.refresh(backend), color)
+    }
+}
+
+/// Returns the input index of a piece on a square, seen from the perspective of `perspective`.
+fn feature(perspective: Color, square: Square, piece: Piece) -> usize {
+    let square = match perspective {
+        Color::White => square,
+        Color::Black => square.flipped(),
+    };
+    let owner = if piece.color() == perspective { 0 } else { 2 };
+    let kind = usize::from(piece.is_king());
+    let index = square.number().map_or(0, |number| number as usize - 1);
+    (owner + kind) * 32 + index
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_i16s(reader: &mut impl Read, count: usize) -> io::Result<Vec<i16>> {
+    let mut bytes = vec![0; count * 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes.chunks_exact(2).map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]])).collect())
+}
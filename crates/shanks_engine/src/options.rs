@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+/// Default maximum depth of the iterative deepening search.
+pub const DEFAULT_DEPTH: usize = 16;
+
+/// Tunable switches for the search.
+///
+/// Every enhancement on top of plain alpha-beta can be turned off individually,
+/// which makes it possible to measure its effect in self-play matches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineOptions {
+    /// The maximum depth the iterative deepening loop searches to.
+    pub depth: usize,
+    /// Search all but the first move of a node with a null window and only
+    /// re-search with the full window if the move turns out to be better.
+    pub pvs: bool,
+    /// Start every iteration with a narrow window around the previous score.
+    pub aspiration: bool,
+    /// The initial half-width of the aspiration window.
+    pub aspiration_window: f64,
+    /// Search late quiet moves with a reduced depth.
+    pub lmr: bool,
+    /// Search one ply deeper when the side to move has a single legal ply or must capture,
+    /// so forced sequences do not count against the depth. The extensions along a line
+    /// add up to at most the depth of the iteration.
+    pub extensions: bool,
+    /// Number of entries in the transposition table.
+    pub tt_size: usize,
+    /// Number of threads searching in parallel. A single thread searches deterministically.
+    pub threads: usize,
+    /// Maximum number of nodes each thread searches before it stops. The first
+    /// iteration is always completed, so a search never ends without a move.
+    pub nodes: Option<u64>,
+    /// Fixed time per move. Like the node limit, it does not cut the first iteration short.
+    pub movetime: Option<Duration>,
+    /// Number of best lines [`Engine::analyze`](crate::Engine::analyze) reports.
+    pub multi_pv: usize,
+}
+
+impl Default for EngineOptions {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_DEPTH,
+            pvs: true,
+            aspiration: true,
+            aspiration_window: 0.5,
+            lmr: true,
+            extensions: true,
+            tt_size: 1 << 20,
+            threads: 1,
+            nodes: None,
+            movetime: None,
+            multi_pv: 1,
+        }
+    }
+}
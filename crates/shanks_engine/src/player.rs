@@ -0,0 +1,48 @@
+use shanks_core::board::Board;
+
+use crate::{Engine, SearchResult, TimeControl};
+
+/// Anything that chooses plies in a game: the alpha-beta [`Engine`], the
+/// [`Mcts`](crate::Mcts) engine, the network-guided [`ZeroPlayer`](crate::ZeroPlayer) or one
+/// of the baseline players, [`RandomPlayer`](crate::RandomPlayer),
+/// [`GreedyPlayer`](crate::GreedyPlayer) and [`MinimaxPlayer`](crate::MinimaxPlayer).
+/// Matches are played between players, so any two can be compared against each other.
+///
+/// A player searches within its own limits, like the depth or time per move it was
+/// created with, unless it is given a clock.
+pub trait Player {
+    /// Returns a short description for match reports, e.g. `alpha-beta depth 6`.
+    fn name(&self) -> String;
+
+    /// Chooses a ply for the side to move. The principal variation of the result starts
+    /// with the chosen ply and is empty if the side to move has no legal plies.
+    fn think(&mut self, board: &Board) -> SearchResult;
+
+    /// Like [`Player::think`], but budgets the time for the move from the clock of the
+    /// side to move. Players that are not limited by time ignore the clock.
+    fn think_with_clock(&mut self, board: &Board, clock: &TimeControl) -> SearchResult {
+        let _ = clock;
+        self.think(board)
+    }
+
+    /// Forgets what was learned in the previous game.
+    fn new_game(&mut self) {}
+}
+
+impl Player for Engine {
+    fn name(&self) -> String {
+        format!("alpha-beta depth {}", self.options().depth)
+    }
+
+    fn think(&mut self, board: &Board) -> SearchResult {
+        Engine::think(self, board)
+    }
+
+    fn think_with_clock(&mut self, board: &Board, clock: &TimeControl) -> SearchResult {
+        Engine::think_with_clock(self, board, clock)
+    }
+
+    fn new_game(&mut self) {
+        self.clear();
+    }
+}
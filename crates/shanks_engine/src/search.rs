@@ -0,0 +1,523 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use shanks_core::board::{Backend, Color, Ply};
+
+use crate::{
+    eval::Evaluator,
+    nnue::{Accumulator, Network},
+    stats::{SearchInfo, SearchStats},
+    tablebase::{Outcome, Tablebase},
+    time::TimeManager,
+    tt::{quantize, Bound, TTEntry, TranspositionTable},
+    EngineOptions,
+};
+
+/// Score of a won position, reduced by the number of plies it takes to get there.
+pub const WIN_SCORE: f64 = 1_000_000.0;
+
+pub(crate) const MAX_PLY: usize = 256;
+/// Width of the window used for the scout searches of PVS, below the resolution of scores.
+const NULL_WINDOW: f64 = 1e-6;
+/// Number of moves at a node that are always searched to full depth.
+const LMR_FULL_DEPTH_MOVES: usize = 3;
+/// Minimum remaining depth at which late move reductions are applied.
+const LMR_MIN_DEPTH: usize = 3;
+/// The clock is read once every this many nodes.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// The outcome of a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// The score of the position from the point of view of the side to move.
+    pub score: f64,
+    /// The depth of the last completed iteration.
+    pub depth: usize,
+    /// The principal variation, starting with the best ply.
+    pub pv: Vec<Ply>,
+    /// The number of nodes visited.
+    pub nodes: u64,
+    /// The number of positions resolved by the endgame tablebase.
+    pub tb_hits: u64,
+}
+
+impl SearchResult {
+    /// Returns the best ply for the side to move, or `None` if it has no legal plies.
+    pub fn best_ply(&self) -> Option<&Ply> {
+        self.pv.first()
+    }
+
+    /// Returns the principal variation as a sequence of plies, written like [`Ply::notation`].
+    pub fn pv_notation(&self) -> String {
+        self.pv.iter().map(Ply::notation).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Everything the threads of a search share.
+#[derive(Clone, Copy)]
+pub(crate) struct SearchContext<'a> {
+    pub(crate) options: &'a EngineOptions,
+    pub(crate) evaluator: &'a dyn Evaluator,
+    /// Replaces the evaluator if a network is loaded.
+    pub(crate) network: Option<&'a Network>,
+    pub(crate) tt: &'a TranspositionTable,
+    pub(crate) tablebase: Option<&'a Tablebase>,
+    pub(crate) stop: &'a AtomicBool,
+    /// Set from outside to stop the search, see [`Engine::set_abort`](crate::Engine::set_abort).
+    pub(crate) abort: Option<&'a AtomicBool>,
+    /// The time limits of the search, if it is limited by time.
+    pub(crate) time: Option<&'a TimeManager>,
+    /// Root plies left out of the search, the best lines found before in a MultiPV search.
+    pub(crate) excluded: &'a [Ply],
+}
+
+/// The state of a single search thread.
+///
+/// All threads of a search share the transposition table. Helper threads of the
+/// Lazy SMP search stop as soon as the main thread sets the stop flag, the main
+/// thread itself always finishes its iterations, which keeps single threaded
+/// searches deterministic.
+pub(crate) struct Searcher<'a> {
+    options: &'a EngineOptions,
+    evaluator: &'a dyn Evaluator,
+    network: Option<&'a Network>,
+    tt: &'a TranspositionTable,
+    tablebase: Option<&'a Tablebase>,
+    stop: &'a AtomicBool,
+    abort: Option<&'a AtomicBool>,
+    time: Option<&'a TimeManager>,
+    excluded: &'a [Ply],
+    helper: bool,
+
+    /// Triangular table holding the principal variation found below each ply.
+    pv: Vec<Vec<Ply>>,
+    /// Network accumulators of the positions along the current line, indexed by ply.
+    accumulators: Vec<Accumulator>,
+    pub(crate) stats: SearchStats,
+    /// The depth of the running iteration.
+    root_depth: usize,
+    /// The depth of the last completed iteration.
+    completed_depth: usize,
+    /// Set once the node or time limit is reached or the search is aborted.
+    stopped: bool,
+}
+
+impl<'a> Searcher<'a> {
+    pub(crate) fn new(context: SearchContext<'a>, helper: bool) -> Self {
+        Self {
+            options: context.options,
+            evaluator: context.evaluator,
+            network: context.network,
+            tt: context.tt,
+            tablebase: context.tablebase,
+            stop: context.stop,
+            abort: context.abort,
+            time: context.time,
+            excluded: context.excluded,
+            helper,
+            pv: vec![Vec::new(); MAX_PLY + 2],
+            accumulators: Vec::new(),
+            stats: SearchStats::default(),
+            root_depth: 0,
+            completed_depth: 0,
+            stopped: false,
+        }
+    }
+
+    /// Runs the iterative deepening loop from `start_depth` up to the configured depth,
+    /// calling `on_info` after every completed iteration.
+    pub(crate) fn iterate(
+        &mut self,
+        backend: &dyn Backend,
+        color: Color,
+        start_depth: usize,
+        on_info: &mut dyn FnMut(&SearchInfo),
+    ) -> SearchResult {
+        let mut result = SearchResult { score: 0.0, depth: 0, pv: Vec::new(), nodes: 0, tb_hits: 0 };
+        let start = Instant::now();
+        self.prepare(backend);
+        self.completed_depth = 0;
+        self.stopped = false;
+        let mut previous_nodes = None;
+        // Decaying count of the iterations that changed the best ply, for the time manager.
+        let mut changes = 0.0;
+        for depth in start_depth..=self.options.depth {
+            let nodes_before = self.stats.nodes;
+            let score = self.aspiration_search(backend, color, depth, result.score);
+            if self.aborted() {
+                break;
+            }
+            self.completed_depth = depth;
+            let previous_score = (result.depth > 0).then_some(result.score);
+            changes /= 2.0;
+            if result.pv.first().is_some_and(|best| self.pv[0].first() != Some(best)) {
+                changes += 1.0;
+            }
+            result.score = score;
+            result.depth = depth;
+            result.pv = self.pv[0].clone();
+
+            self.stats.elapsed = start.elapsed();
+            let nodes = self.stats.nodes - nodes_before;
+            on_info(&SearchInfo {
+                line: self.excluded.len() + 1,
+                depth,
+                score,
+                pv: result.pv.clone(),
+                stats: self.stats,
+                branching_factor: previous_nodes.map(|previous: u64| nodes as f64 / previous.max(1) as f64),
+            });
+            previous_nodes = Some(nodes);
+
+            if result.score.abs() >= WIN_SCORE - MAX_PLY as f64 {
+                break;
+            }
+            if self.time.is_some_and(|time| !time.next_iteration(changes, score, previous_score)) {
+                break;
+            }
+        }
+        self.stats.elapsed = start.elapsed();
+        result.nodes = self.stats.nodes;
+        result.tb_hits = self.stats.tb_hits;
+        result
+    }
+
+    /// Searches the position to a fixed depth with a full window and returns the score
+    /// from the point of view of `color`, the side to move.
+    pub(crate) fn search(&mut self, backend: &dyn Backend, color: Color, depth: usize) -> f64 {
+        self.prepare(backend);
+        self.root_depth = depth;
+        self.alpha_beta(backend, color, depth, 0, 0, -WIN_SCORE, WIN_SCORE)
+    }
+
+    /// Sets up the network accumulators for a search from the given root position.
+    fn prepare(&mut self, backend: &dyn Backend) {
+        if let Some(network) = self.network {
+            self.accumulators = vec![network.refresh(backend); MAX_PLY + 2];
+        }
+    }
+
+    /// Makes the ply on a copy of the backend, updating the accumulator of the child's ply.
+    fn make(&mut self, backend: &dyn Backend, child_ply: &Ply, ply: usize) -> Box<dyn Backend> {
+        if let Some(network) = self.network {
+            let (head, tail) = self.accumulators.split_at_mut(ply + 1);
+            network.update(&head[ply], &mut tail[0], backend, child_ply);
+        }
+        let mut child = backend.clone_box();
+        child.ply(child_ply.clone());
+        child
+    }
+
+    /// Evaluates the position, rounded to the scores the transposition table stores exactly.
+    fn static_eval(&self, backend: &dyn Backend, color: Color, ply: usize) -> f64 {
+        quantize(match self.network {
+            Some(network) => network.evaluate_accumulator(&self.accumulators[ply], color),
+            None => self.evaluator.evaluate(backend, color),
+        })
+    }
+
+    fn quiescence(&mut self, backend: &dyn Backend, color: Color, ply: usize, mut alpha: f64, beta: f64) -> f64 {
+        self.stats.seldepth = self.stats.seldepth.max(ply);
+        let plies = backend.get_legal_plies(color);
+        if plies.is_empty() {
+            return -(WIN_SCORE - ply as f64);
+        }
+        if plies[0].captures().is_empty() || ply >= MAX_PLY {
+            return self.static_eval(backend, color, ply);
+        }
+
+        let mut best_score = -WIN_SCORE;
+        for capture in plies {
+            self.stats.nodes += 1;
+            let child = self.make(backend, &capture, ply);
+            let score = -self.quiescence(child.as_ref(), color.opposite(), ply + 1, -beta, -alpha);
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best_score
+    }
+
+    fn aborted(&self) -> bool {
+        self.stopped || (self.helper && self.stop.load(Ordering::Relaxed))
+    }
+
+    fn aspiration_search(&mut self, backend: &dyn Backend, color: Color, depth: usize, previous: f64) -> f64 {
+        if !self.options.aspiration || depth == 1 || previous.abs() >= WIN_SCORE - MAX_PLY as f64 {
+            return self.search(backend, color, depth);
+        }
+
+        self.root_depth = depth;
+        let mut window = self.options.aspiration_window;
+        let mut alpha = previous - window;
+        let mut beta = previous + window;
+        loop {
+            let score = self.alpha_beta(backend, color, depth, 0, 0, alpha, beta);
+            if score <= alpha {
+                alpha = (score - window).max(-WIN_SCORE);
+            } else if score >= beta {
+                beta = (score + window).min(WIN_SCORE);
+            } else {
+                return score;
+            }
+            window *= 2.0;
+            if window > WIN_SCORE / 4.0 {
+                return self.search(backend, color, depth);
+            }
+        }
+    }
+
+    /// Searches the child reached by the `move_number`-th ply of a node, applying
+    /// principal variation search and late move reductions where enabled.
+    #[allow(clippy::too_many_arguments)]
+    fn search_child(
+        &mut self,
+        child: &dyn Backend,
+        color: Color,
+        depth: usize,
+        ply: usize,
+        extensions: usize,
+        alpha: f64,
+        beta: f64,
+        move_number: usize,
+        quiet: bool,
+    ) -> f64 {
+        if move_number == 0 {
+            return -self.alpha_beta(child, color, depth, ply, extensions, -beta, -alpha);
+        }
+
+        let reduction =
+            usize::from(self.options.lmr && quiet && move_number >= LMR_FULL_DEPTH_MOVES && depth + 1 >= LMR_MIN_DEPTH);
+
+        if !self.options.pvs {
+            if reduction > 0 {
+                let score = -self.alpha_beta(child, color, depth - reduction, ply, extensions, -beta, -alpha);
+                if score <= alpha {
+                    return score;
+                }
+            }
+            return -self.alpha_beta(child, color, depth, ply, extensions, -beta, -alpha);
+        }
+
+        let mut score =
+            -self.alpha_beta(child, color, depth - reduction, ply, extensions, -alpha - NULL_WINDOW, -alpha);
+        if score > alpha && reduction > 0 {
+            score = -self.alpha_beta(child, color, depth, ply, extensions, -alpha - NULL_WINDOW, -alpha);
+        }
+        if score > alpha && score < beta {
+            score = -self.alpha_beta(child, color, depth, ply, extensions, -beta, -alpha);
+        }
+        score
+    }
+
+    /// Searches the position to `depth` plies, with `extensions` plies of extension made on the
+    /// way from the root.
+    #[allow(clippy::too_many_arguments)]
+    fn alpha_beta(
+        &mut self,
+        backend: &dyn Backend,
+        color: Color,
+        depth: usize,
+        ply: usize,
+        extensions: usize,
+        mut alpha: f64,
+        mut beta: f64,
+    ) -> f64 {
+        self.stats.nodes += 1;
+        self.stats.seldepth = self.stats.seldepth.max(ply);
+        self.pv[ply].clear();
+        let aborted = self.abort.is_some_and(|abort| abort.load(Ordering::Relaxed))
+            || self.options.nodes.is_some_and(|nodes| self.stats.nodes >= nodes)
+            || (self.stats.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+                && self.time.is_some_and(TimeManager::out_of_time));
+        if aborted && self.completed_depth > 0 {
+            self.stopped = true;
+        }
+        if self.aborted() {
+            return 0.0;
+        }
+
+        let mut plies = backend.get_legal_plies(color);
+        if plies.is_empty() {
+            // The side to move has no pieces or is blocked and therefore lost.
+            return -(WIN_SCORE - ply as f64);
+        }
+        // A single reply or a capture is forced, so the line is searched one ply deeper.
+        // Plies excluded at the root are still legal, so they count for a single reply.
+        let forced = plies.len() == 1 || !plies[0].captures().is_empty();
+        if ply == 0 && !self.excluded.is_empty() {
+            plies.retain(|root_ply| !self.excluded.contains(root_ply));
+        }
+
+        // The root is left to the engine, which picks a ply preserving the tablebase result.
+        if let Some(outcome) = self.tablebase.filter(|_| ply > 0).and_then(|tablebase| tablebase.probe(backend, color))
+        {
+            self.stats.tb_hits += 1;
+            return tablebase_score(outcome, ply);
+        }
+
+        if ply >= MAX_PLY {
+            return self.static_eval(backend, color, ply);
+        }
+        if depth == 0 {
+            return self.quiescence(backend, color, ply, alpha, beta);
+        }
+
+        let key = TranspositionTable::key(backend.state_hash(), color);
+        let entry = self.tt.probe(key);
+        self.stats.tt_probes += 1;
+        self.stats.tt_hits += u64::from(entry.is_some());
+        if let Some(entry) = entry.filter(|_| ply > 0) {
+            if entry.depth as usize >= depth {
+                let score = score_from_tt(entry.score, ply);
+                match entry.bound {
+                    Bound::Exact => return score,
+                    Bound::Lower => alpha = alpha.max(score),
+                    Bound::Upper => beta = beta.min(score),
+                }
+                if alpha >= beta {
+                    return score;
+                }
+            }
+        }
+
+        // A line is extended by at most the iteration's depth.
+        let extension = usize::from(self.options.extensions && forced && extensions < self.root_depth);
+
+        let original_alpha = alpha;
+        let mut best_score = -WIN_SCORE;
+        let mut best_index = 0;
+        self.stats.expanded += 1;
+        // The best index of the entry counts the full root, not the plies left after exclusion.
+        let hint = entry.and_then(|entry| entry.best).filter(|_| ply > 0 || self.excluded.is_empty());
+        for (i, (index, child_ply)) in order_plies(plies, hint).into_iter().enumerate() {
+            let quiet = child_ply.captures().is_empty() && !child_ply.promoted();
+            let child = self.make(backend, &child_ply, ply);
+
+            let child_depth = depth - 1 + extension;
+            let score = self.search_child(
+                child.as_ref(),
+                color.opposite(),
+                child_depth,
+                ply + 1,
+                extensions + extension,
+                alpha,
+                beta,
+                i,
+                quiet,
+            );
+            if score > best_score {
+                best_score = score;
+                best_index = index;
+            }
+            if score > alpha || (ply == 0 && i == 0) {
+                let (head, tail) = self.pv.split_at_mut(ply + 1);
+                head[ply].clear();
+                head[ply].push(child_ply);
+                head[ply].extend_from_slice(&tail[0]);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                self.stats.cutoffs += 1;
+                self.stats.first_move_cutoffs += u64::from(i == 0);
+                break;
+            }
+        }
+
+        if self.aborted() {
+            return 0.0;
+        }
+
+        // With plies excluded, the root's score and best index are those of the remaining
+        // plies only, which would mislead the next search of the full root.
+        if ply == 0 && !self.excluded.is_empty() {
+            return best_score;
+        }
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.tt.store(TTEntry {
+            key,
+            depth: depth.min(u8::MAX as usize) as u8,
+            score: score_to_tt(best_score, ply),
+            bound,
+            best: Some(best_index as u8),
+        });
+        best_score
+    }
+}
+
+/// Returns the score of the position from the point of view of `color` once all pending
+/// captures are resolved. Captures are forced in checkers, so there is no stand pat.
+pub fn quiescence(evaluator: &dyn Evaluator, backend: &dyn Backend, color: Color) -> f64 {
+    let options = EngineOptions::default();
+    let tt = TranspositionTable::new(1);
+    let stop = AtomicBool::new(false);
+    let context = SearchContext {
+        options: &options,
+        evaluator,
+        network: None,
+        tt: &tt,
+        tablebase: None,
+        stop: &stop,
+        abort: None,
+        time: None,
+        excluded: &[],
+    };
+    Searcher::new(context, false).quiescence(backend, color, 0, -WIN_SCORE, WIN_SCORE)
+}
+
+/// Returns the search score of a position with a known outcome `ply` plies from the root.
+/// Wins and losses are scored like the end of the game they lead to.
+pub(crate) fn tablebase_score(outcome: Outcome, ply: usize) -> f64 {
+    match outcome {
+        Outcome::Win(distance) => WIN_SCORE - (ply + distance as usize).min(MAX_PLY) as f64,
+        Outcome::Loss(distance) => -(WIN_SCORE - (ply + distance as usize).min(MAX_PLY) as f64),
+        Outcome::Draw => 0.0,
+    }
+}
+
+/// Orders the plies for the search, keeping track of their original index.
+/// The ply from the transposition table comes first, followed by promotions.
+fn order_plies(plies: Vec<Ply>, tt_best: Option<u8>) -> Vec<(usize, Ply)> {
+    let mut ordered: Vec<(usize, Ply)> = plies.into_iter().enumerate().collect();
+    ordered.sort_by_key(|(index, ply)| {
+        if Some(*index as u8) == tt_best {
+            0
+        } else if ply.promoted() {
+            1
+        } else {
+            2
+        }
+    });
+    ordered
+}
+
+/// Win scores depend on the distance to the root, so they are stored relative to the node.
+fn score_to_tt(score: f64, ply: usize) -> f64 {
+    if score >= WIN_SCORE - MAX_PLY as f64 {
+        score + ply as f64
+    } else if score <= -(WIN_SCORE - MAX_PLY as f64) {
+        score - ply as f64
+    } else {
+        score
+    }
+}
+
+fn score_from_tt(score: f64, ply: usize) -> f64 {
+    if score >= WIN_SCORE - MAX_PLY as f64 {
+        score - ply as f64
+    } else if score <= -(WIN_SCORE - MAX_PLY as f64) {
+        score + ply as f64
+    } else {
+        score
+    }
+}
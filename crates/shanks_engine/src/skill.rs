@@ -0,0 +1,134 @@
+use rand::Rng;
+
+use crate::{play_match, Engine, EngineOptions, MatchConfig, MatchStats, SearchResult, MAN_VALUE};
+
+/// The strongest skill level, which plays at full strength.
+pub const MAX_SKILL: u8 = 20;
+/// The rating of the reference engine the skill levels are calibrated against.
+pub const REFERENCE_ELO: f64 = 1500.0;
+
+/// Maximum search depth of the levels below [`MAX_SKILL`].
+const DEPTHS: [usize; MAX_SKILL as usize - 1] = [1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 9, 10, 11, 12];
+/// Number of best root lines a weakened engine chooses from.
+const LINES: usize = 4;
+/// Ratings of the skill levels, measured by [`calibrate`] with 20 ballots against a
+/// depth 6 reference. Neighbouring levels whose results came out of order share their
+/// average. The strongest levels all beat or draw the reference alike, so they share a
+/// rating as well.
+const ELO: [f64; MAX_SKILL as usize] = [
+    859.0, 859.0, 1098.0, 1098.0, 1157.0, 1157.0, 1291.0, 1291.0, 1405.0, 1405.0, 1500.0, 1500.0, 1565.0, 1565.0,
+    1578.0, 1658.0, 1658.0, 1658.0, 1658.0, 1658.0,
+];
+
+/// A playing strength between 1 and [`MAX_SKILL`].
+///
+/// Lower levels search less deeply and pick among the best root lines at random,
+/// preferring better scores. The randomness shrinks as the level rises, at the highest
+/// level the engine plays at full strength.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Skill {
+    level: u8,
+}
+
+impl Skill {
+    /// Creates a skill level, clamped to 1..=[`MAX_SKILL`].
+    pub fn new(level: u8) -> Self {
+        Self { level: level.clamp(1, MAX_SKILL) }
+    }
+
+    /// Returns the lowest level rated at least `elo`, or the highest level if none is.
+    pub fn from_elo(elo: f64) -> Self {
+        let level = ELO.iter().position(|&rating| rating >= elo).map_or(MAX_SKILL as usize, |index| index + 1);
+        Self::new(level as u8)
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Returns the approximate rating of the level.
+    pub fn elo(&self) -> f64 {
+        ELO[self.level as usize - 1]
+    }
+
+    /// Returns `true` if the level plays below full strength.
+    pub fn is_limited(&self) -> bool {
+        self.level < MAX_SKILL
+    }
+
+    /// Restricts the depth and node count of the options to the level.
+    pub fn limit(&self, options: EngineOptions) -> EngineOptions {
+        if !self.is_limited() {
+            return options;
+        }
+        let level = self.level as u64;
+        EngineOptions {
+            depth: options.depth.min(DEPTHS[self.level as usize - 1]),
+            nodes: Some(options.nodes.map_or(100 * level.pow(3), |nodes| nodes.min(100 * level.pow(3)))),
+            ..options
+        }
+    }
+
+    /// Returns the number of root lines the level chooses from.
+    pub fn lines(&self) -> usize {
+        if self.is_limited() {
+            LINES
+        } else {
+            1
+        }
+    }
+
+    /// Returns the score difference, in evaluation units, at which a worse line is chosen
+    /// `e` times less often than the best one.
+    pub fn temperature(&self) -> f64 {
+        let steps = (MAX_SKILL - self.level) as f64;
+        2.0 * MAN_VALUE * steps * steps / ((MAX_SKILL - 1) as f64).powi(2)
+    }
+
+    /// Picks one of the lines, ranked best first, at random. Each line is weighted by
+    /// `exp(-loss / temperature)`, where the loss is its score difference to the best line.
+    pub fn pick(&self, mut lines: Vec<SearchResult>, rng: &mut impl Rng) -> Option<SearchResult> {
+        let best = lines.first()?.score;
+        let temperature = self.temperature();
+        if temperature <= 0.0 {
+            return lines.into_iter().next();
+        }
+        let weights: Vec<f64> = lines.iter().map(|line| ((line.score - best) / temperature).exp()).collect();
+        let mut target = rng.gen::<f64>() * weights.iter().sum::<f64>();
+        let index = weights
+            .iter()
+            .position(|&weight| {
+                target -= weight;
+                target < 0.0
+            })
+            .unwrap_or(0);
+        Some(lines.swap_remove(index))
+    }
+}
+
+impl std::fmt::Display for Skill {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.level)
+    }
+}
+
+/// Plays a match of every skill level against the reference engine and returns the
+/// measured ratings, with the reference rated [`REFERENCE_ELO`]. `player` is weakened to
+/// each level in turn, `on_level` receives the result of every level.
+pub fn calibrate<F>(player: &mut Engine, reference: &mut Engine, config: &MatchConfig, mut on_level: F) -> Vec<f64>
+where
+    F: FnMut(Skill, &MatchStats, f64),
+{
+    (1..=MAX_SKILL)
+        .map(|level| {
+            let skill = Skill::new(level);
+            player.set_skill(Some(skill));
+            let stats = play_match(player, reference, config, |_| {});
+            // Half a point on either side keeps the rating finite for clean sweeps.
+            let score = (stats.wins as f64 + stats.draws as f64 / 2.0 + 0.5) / (stats.games() as f64 + 1.0);
+            let elo = REFERENCE_ELO + 400.0 * (score / (1.0 - score)).log10();
+            on_level(skill, &stats, elo);
+            elo
+        })
+        .collect()
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::{hash_map::Entry as MapEntry, HashMap, VecDeque},
+    fmt,
+};
+
+use shanks_core::board::{Backend, Board, Color, Ply};
+
+use crate::{search::MAX_PLY, tt::TranspositionTable};
+
+/// Proof and disproof numbers of proven or disproven positions.
+const INFINITY: u32 = u32::MAX;
+/// The first ply limit of the iterative deepening, which doubles until `MAX_PLY`.
+const FIRST_LIMIT: usize = 8;
+/// Mixed into the keys of the table so the same position with a different number of
+/// remaining plies gets its own entry.
+const REMAINING_KEY: u64 = 0xff51_afd7_ed55_8ccd;
+/// Mixed into the keys of the table while black attacks, since the numbers of a position
+/// depend on who tries to win.
+const BLACK_ATTACKS: u64 = 0xc4ce_b9fe_1a85_ec53;
+/// Rough memory use of a position in the exhaustive analysis: its key in the index and
+/// the lists of its successors and predecessors.
+const BYTES_PER_POSITION: usize = 160;
+
+/// What a [`Solver`] proved about a position, for the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Proof {
+    Win,
+    Loss,
+    /// Neither side can force a win.
+    Draw,
+    /// Nothing was proven within the node and memory limits.
+    Unknown,
+}
+
+impl fmt::Display for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Proof::Win => write!(f, "win"),
+            Proof::Loss => write!(f, "loss"),
+            Proof::Draw => write!(f, "draw"),
+            Proof::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Settings of the [`Solver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverOptions {
+    /// Memory in bytes for the positions of the exhaustive analysis, or else for the
+    /// table of proof and disproof numbers. Once the table is full, entries of smaller
+    /// subtrees are replaced, which makes the search slower but not wrong.
+    pub memory: usize,
+    /// Maximum number of nodes to expand before giving up.
+    pub nodes: Option<u64>,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self { memory: 64 << 20, nodes: None }
+    }
+}
+
+/// The result of [`Solver::solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveResult {
+    pub proof: Proof,
+    /// For a win or loss, a line to the end of the game in which the winner plays the
+    /// quickest proven ply and the loser resists as long as the proof shows. It ends early
+    /// if the table has forgotten the rest of the proof. Empty for draws.
+    pub line: Vec<Ply>,
+    /// Number of nodes expanded.
+    pub nodes: u64,
+}
+
+/// An entry of the table: the proof and disproof numbers of a position for its side to move
+/// with some number of remaining plies, and the number of nodes its last search expanded.
+#[derive(Debug, Clone, Copy, Default)]
+struct Entry {
+    key: u64,
+    phi: u32,
+    delta: u32,
+    work: u32,
+}
+
+/// Proves whether the side to move wins, loses or draws, without any evaluation.
+///
+/// Checkers has no draw rule, so a draw means that neither side can force a win. Proving
+/// that takes reasoning about the cycles of the game graph, which only an exhaustive
+/// analysis does right. So if all positions reachable from the root fit into the memory
+/// limit, the solver enumerates them and solves them backwards from the positions where a
+/// side has no legal plies, like the tablebase generator. The result is exact, and the
+/// line follows the shortest win against the longest defence.
+///
+/// Larger positions are searched by depth-first proof-number search (df-pn). Every
+/// position has a proof number φ, the number of leaves that at least have to be solved to
+/// prove a win for its side to move, and a disproof number δ for refuting it. The search
+/// expands the most proving child within thresholds, like best-first proof-number search,
+/// but depth-first and with a fixed-size table instead of a tree in memory. It proves wins
+/// within a limit of plies, doubling the limit up to the maximum search depth, first for
+/// the side to move and then for its opponent. Positions are stored together with their
+/// number of remaining plies, which keeps the search graph free of cycles and the stored
+/// numbers valid wherever a position recurs. Draws remain unknown this way.
+pub struct Solver {
+    options: SolverOptions,
+    table: Vec<Entry>,
+    /// The side trying to prove a win in the current search.
+    attacker: Color,
+    nodes: u64,
+}
+
+impl Solver {
+    pub fn new(options: SolverOptions) -> Self {
+        Self { options, table: Vec::new(), attacker: Color::White, nodes: 0 }
+    }
+
+    pub fn options(&self) -> &SolverOptions {
+        &self.options
+    }
+
+    /// Proves whether the side to move wins, loses or draws.
+    pub fn solve(&mut self, board: &Board) -> SolveResult {
+        let (backend, color) = (board.get_backend(), board.to_move());
+        self.nodes = 0;
+        if let Some(result) = self.solve_exhaustively(backend, color) {
+            return result;
+        }
+
+        let size = (self.options.memory / std::mem::size_of::<Entry>()).max(1);
+        self.table = vec![Entry::default(); size];
+        let mut result = SolveResult { proof: Proof::Unknown, line: Vec::new(), nodes: 0 };
+        let mut limit = FIRST_LIMIT;
+        'limits: loop {
+            for (attacker, proof) in [(color, Proof::Win), (color.opposite(), Proof::Loss)] {
+                self.attacker = attacker;
+                let (phi, delta) = self.mid(backend, color, limit, INFINITY, INFINITY);
+                if (if attacker == color { phi } else { delta }) == 0 {
+                    result = SolveResult { proof, line: self.line(backend, color, limit), nodes: 0 };
+                    break 'limits;
+                }
+                if self.out_of_nodes() {
+                    break 'limits;
+                }
+            }
+            if limit >= MAX_PLY {
+                break;
+            }
+            limit = (limit * 2).min(MAX_PLY);
+        }
+        self.table = Vec::new();
+        SolveResult { nodes: self.nodes, ..result }
+    }
+
+    /// Searches the position with `remaining` plies left until its proof number reaches
+    /// `th_phi` or its disproof number reaches `th_delta`, and returns both. A position
+    /// without remaining plies is a failure for the attacker, unless the game is over.
+    fn mid(&mut self, backend: &dyn Backend, color: Color, remaining: usize, th_phi: u32, th_delta: u32) -> (u32, u32) {
+        self.nodes += 1;
+        let key = self.key(backend, color, remaining);
+        let plies = backend.get_legal_plies(color);
+        if plies.is_empty() {
+            self.store(key, INFINITY, 0, 1);
+            return (INFINITY, 0);
+        }
+        if remaining == 0 {
+            let (phi, delta) = if color == self.attacker { (INFINITY, 0) } else { (0, INFINITY) };
+            self.store(key, phi, delta, 1);
+            return (phi, delta);
+        }
+
+        let children: Vec<Box<dyn Backend>> = plies
+            .into_iter()
+            .map(|ply| {
+                let mut child = backend.clone_box();
+                child.ply(ply);
+                child
+            })
+            .collect();
+        let mut values: Vec<(u32, u32)> = children
+            .iter()
+            .map(|child| {
+                let entry = self.probe(child.as_ref(), color.opposite(), remaining - 1);
+                entry.map_or((1, 1), |entry| (entry.phi, entry.delta))
+            })
+            .collect();
+
+        let start = self.nodes;
+        let (phi, delta) = loop {
+            // The side to move wins if some child is lost for its side to move.
+            let phi = values.iter().map(|&(_, delta)| delta).min().expect("there are children");
+            let delta = values.iter().fold(0, |sum: u32, &(phi, _)| sum.saturating_add(phi));
+            if phi >= th_phi || delta >= th_delta || self.out_of_nodes() {
+                break (phi, delta);
+            }
+
+            let (mut best, mut second) = (0, INFINITY);
+            for (index, &(_, delta)) in values.iter().enumerate().skip(1) {
+                if delta < values[best].1 {
+                    second = values[best].1;
+                    best = index;
+                } else if delta < second {
+                    second = delta;
+                }
+            }
+            let (child_phi, _) = values[best];
+            let child_th_phi = (th_delta - delta).saturating_add(child_phi);
+            let child_th_delta = th_phi.min(second.saturating_add(1));
+            let child = children[best].as_ref();
+            values[best] = self.mid(child, color.opposite(), remaining - 1, child_th_phi, child_th_delta);
+        };
+
+        let work = (self.nodes - start + 1).min(u32::MAX as u64) as u32;
+        self.store(key, phi, delta, work);
+        (phi, delta)
+    }
+
+    /// Follows the proof in the table from the root, which was searched with `limit` plies.
+    fn line(&self, backend: &dyn Backend, mut color: Color, limit: usize) -> Vec<Ply> {
+        let mut line = Vec::new();
+        let mut backend = backend.clone_box();
+        for remaining in (0..limit).rev() {
+            let winner = color == self.attacker;
+            let next = backend
+                .get_legal_plies(color)
+                .into_iter()
+                .filter_map(|ply| {
+                    let mut child = backend.clone_box();
+                    child.ply(ply.clone());
+                    let entry = self.probe(child.as_ref(), color.opposite(), remaining)?;
+                    let proven = if winner { entry.delta == 0 } else { entry.phi == 0 };
+                    proven.then_some((entry.work, ply, child))
+                })
+                .max_by_key(|&(work, ..)| if winner { u32::MAX - work } else { work });
+            let Some((_, ply, child)) = next else {
+                break;
+            };
+            line.push(ply);
+            backend = child;
+            color = color.opposite();
+        }
+        line
+    }
+
+    fn out_of_nodes(&self) -> bool {
+        self.options.nodes.is_some_and(|limit| self.nodes >= limit)
+    }
+
+    fn key(&self, backend: &dyn Backend, color: Color, remaining: usize) -> u64 {
+        let key =
+            TranspositionTable::key(backend.state_hash(), color) ^ (remaining as u64 + 1).wrapping_mul(REMAINING_KEY);
+        match self.attacker {
+            Color::White => key,
+            Color::Black => key ^ BLACK_ATTACKS,
+        }
+    }
+
+    fn probe(&self, backend: &dyn Backend, color: Color, remaining: usize) -> Option<Entry> {
+        let key = self.key(backend, color, remaining);
+        let entry = self.table[self.index(key)];
+        (entry.work > 0 && entry.key == key).then_some(entry)
+    }
+
+    /// Stores the numbers unless the slot holds another position whose search took more work.
+    fn store(&mut self, key: u64, phi: u32, delta: u32, work: u32) {
+        let index = self.index(key);
+        let slot = &mut self.table[index];
+        if slot.key == key || work >= slot.work {
+            *slot = Entry { key, phi, delta, work };
+        }
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.table.len() as u64) as usize
+    }
+
+    /// Solves the position by retrograde analysis of all positions reachable from it.
+    /// Returns `None` if they do not fit into memory or the node limit is reached first.
+    fn solve_exhaustively(&mut self, root: &dyn Backend, color: Color) -> Option<SolveResult> {
+        let capacity = self.options.memory / BYTES_PER_POSITION;
+        let mut index = HashMap::from([(TranspositionTable::key(root.state_hash(), color), 0)]);
+        let mut successors: Vec<Vec<u32>> = Vec::new();
+        let mut queue = VecDeque::from([(root.clone_box(), color)]);
+        while let Some((backend, color)) = queue.pop_front() {
+            self.nodes += 1;
+            if self.out_of_nodes() {
+                return None;
+            }
+            let mut children = Vec::new();
+            for ply in backend.get_legal_plies(color) {
+                let mut child = backend.clone_box();
+                child.ply(ply);
+                let next = index.len() as u32;
+                match index.entry(TranspositionTable::key(child.state_hash(), color.opposite())) {
+                    MapEntry::Occupied(entry) => children.push(*entry.get()),
+                    MapEntry::Vacant(entry) => {
+                        entry.insert(next);
+                        children.push(next);
+                        queue.push_back((child, color.opposite()));
+                    }
+                }
+            }
+            if index.len() > capacity {
+                return None;
+            }
+            successors.push(children);
+        }
+        drop(index);
+
+        // Positions are numbered in the order they were found, which is the order they
+        // were expanded in, so `successors[i]` belongs to position `i`.
+        let mut predecessors = vec![Vec::new(); successors.len()];
+        for (position, children) in successors.iter().enumerate() {
+            for &child in children {
+                predecessors[child as usize].push(position as u32);
+            }
+        }
+
+        // Whether the side to move wins, with the number of plies to the end of the game.
+        let mut outcomes: Vec<Option<(bool, u32)>> = vec![None; successors.len()];
+        let mut unsolved: Vec<usize> = successors.iter().map(Vec::len).collect();
+        let mut queue: VecDeque<usize> = (0..successors.len()).filter(|&position| unsolved[position] == 0).collect();
+        for &position in &queue {
+            outcomes[position] = Some((false, 0));
+        }
+        while let Some(position) = queue.pop_front() {
+            let (won, distance) = outcomes[position].expect("queued positions are solved");
+            for &predecessor in &predecessors[position] {
+                let predecessor = predecessor as usize;
+                if outcomes[predecessor].is_some() {
+                    continue;
+                }
+                if !won {
+                    outcomes[predecessor] = Some((true, distance + 1));
+                    queue.push_back(predecessor);
+                } else {
+                    unsolved[predecessor] -= 1;
+                    if unsolved[predecessor] == 0 {
+                        outcomes[predecessor] = Some((false, distance + 1));
+                        queue.push_back(predecessor);
+                    }
+                }
+            }
+        }
+
+        let proof = match outcomes[0] {
+            Some((true, _)) => Proof::Win,
+            Some((false, _)) => Proof::Loss,
+            None => Proof::Draw,
+        };
+        let mut line = Vec::new();
+        let (mut backend, mut color, mut position) = (root.clone_box(), color, 0);
+        while let Some((won, _)) = outcomes[position] {
+            // The winner moves to a lost position as quickly as possible, the loser to a won one as slowly.
+            let next = backend
+                .get_legal_plies(color)
+                .into_iter()
+                .zip(&successors[position])
+                .filter_map(|(ply, &child)| {
+                    let (child_won, distance) = outcomes[child as usize]?;
+                    (child_won != won).then_some((distance, ply, child))
+                })
+                .max_by_key(|&(distance, ..)| if won { u32::MAX - distance } else { distance });
+            let Some((_, ply, child)) = next else {
+                break;
+            };
+            backend.ply(ply.clone());
+            line.push(ply);
+            color = color.opposite();
+            position = child as usize;
+        }
+        Some(SolveResult { proof, line, nodes: self.nodes })
+    }
+}
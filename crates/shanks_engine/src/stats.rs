@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use shanks_core::board::Ply;
+
+/// Counters collected while searching, used to judge the speed of the search and the
+/// quality of its move ordering.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SearchStats {
+    /// The number of nodes visited, including quiescence nodes.
+    pub nodes: u64,
+    /// The number of positions resolved by the endgame tablebase.
+    pub tb_hits: u64,
+    /// The number of transposition table lookups and how many of them found an entry.
+    pub tt_probes: u64,
+    pub tt_hits: u64,
+    /// The number of nodes whose plies were searched.
+    pub expanded: u64,
+    /// The number of expanded nodes that failed high, and how many of them on the first ply.
+    pub cutoffs: u64,
+    pub first_move_cutoffs: u64,
+    /// The deepest ply reached, including quiescence.
+    pub seldepth: usize,
+    /// The time spent searching.
+    pub elapsed: Duration,
+}
+
+impl SearchStats {
+    /// Returns the number of nodes searched per second.
+    pub fn nps(&self) -> f64 {
+        ratio(self.nodes, self.elapsed.as_secs_f64())
+    }
+
+    /// Returns the share of transposition table lookups that found an entry.
+    pub fn tt_hit_rate(&self) -> f64 {
+        ratio(self.tt_hits, self.tt_probes as f64)
+    }
+
+    /// Returns the share of expanded nodes that failed high.
+    pub fn cutoff_rate(&self) -> f64 {
+        ratio(self.cutoffs, self.expanded as f64)
+    }
+
+    /// Returns the share of fail highs caused by the first ply, a measure of move ordering.
+    pub fn first_move_cutoff_rate(&self) -> f64 {
+        ratio(self.first_move_cutoffs, self.cutoffs as f64)
+    }
+
+    /// Adds the counters of another thread. The elapsed time is left alone, since the
+    /// threads search at the same time.
+    pub fn merge(&mut self, other: &SearchStats) {
+        self.nodes += other.nodes;
+        self.tb_hits += other.tb_hits;
+        self.tt_probes += other.tt_probes;
+        self.tt_hits += other.tt_hits;
+        self.expanded += other.expanded;
+        self.cutoffs += other.cutoffs;
+        self.first_move_cutoffs += other.first_move_cutoffs;
+        self.seldepth = self.seldepth.max(other.seldepth);
+    }
+}
+
+fn ratio(count: u64, total: f64) -> f64 {
+    if total > 0.0 {
+        count as f64 / total
+    } else {
+        0.0
+    }
+}
+
+/// Receives the progress of a search, see [`Engine::set_info_callback`](crate::Engine::set_info_callback).
+pub type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+/// Progress of a search, reported after every completed iteration of the main thread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchInfo {
+    /// The rank of the line being searched, 1 for the best line. Only a MultiPV search
+    /// reports more than one line.
+    pub line: usize,
+    pub depth: usize,
+    /// The score from the point of view of the side to move.
+    pub score: f64,
+    pub pv: Vec<Ply>,
+    /// The counters of the main thread since the start of the search.
+    pub stats: SearchStats,
+    /// The number of nodes of this iteration divided by the number of nodes of the
+    /// previous one, or `None` for the first iteration.
+    pub branching_factor: Option<f64>,
+}
+
+impl SearchInfo {
+    /// Returns the principal variation as a sequence of plies, written like [`Ply::notation`].
+    pub fn pv_notation(&self) -> String {
+        self.pv.iter().map(Ply::notation).collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl std::fmt::Display for SearchInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "multipv {} depth {} seldepth {} score {:.3} nodes {} nps {:.0} tthit {:.1}% cutoffs {:.1}%",
+            self.line,
+            self.depth,
+            self.stats.seldepth,
+            self.score,
+            self.stats.nodes,
+            self.stats.nps(),
+            self.stats.tt_hit_rate() * 100.0,
+            self.stats.cutoff_rate() * 100.0
+        )?;
+        if let Some(branching_factor) = self.branching_factor {
+            write!(f, " ebf {:.2}", branching_factor)?;
+        }
+        write!(f, " pv {}", self.pv_notation())
+    }
+}
@@ -0,0 +1,144 @@
+use shanks_core::board::{Board, FenError, Ply, Square};
+
+use crate::{Engine, SearchResult};
+
+const REGRESSION: &str = include_str!("regression.epd");
+
+/// An error returned when reading a test suite fails, with the number of the offending line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuiteError {
+    /// The position is not valid FEN.
+    InvalidFen(usize, FenError),
+    /// An operation is not of the form `opcode operands;`, or the `id` is not quoted.
+    InvalidOperation(usize, String),
+    /// The position has no `bm` operation.
+    MissingBestMove(usize),
+    /// A best move is not legal in the position.
+    IllegalMove(usize, String),
+}
+
+impl std::fmt::Display for SuiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SuiteError::InvalidFen(line, err) => write!(f, "line {}: invalid FEN: {}", line, err),
+            SuiteError::InvalidOperation(line, operation) => {
+                write!(f, "line {}: invalid operation '{}'", line, operation)
+            }
+            SuiteError::MissingBestMove(line) => write!(f, "line {}: no best move given", line),
+            SuiteError::IllegalMove(line, ply) => write!(f, "line {}: illegal best move '{}'", line, ply),
+        }
+    }
+}
+
+impl std::error::Error for SuiteError {}
+
+/// A position of a test suite with the plies that solve it.
+#[derive(Clone)]
+pub struct TestPosition {
+    pub id: String,
+    pub board: Board,
+    /// The plies that count as a solution, any of them will do.
+    pub best: Vec<Ply>,
+}
+
+impl TestPosition {
+    /// Returns the best plies in notation, separated by spaces.
+    pub fn best_notation(&self) -> String {
+        self.best.iter().map(Ply::notation).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Parses a test suite in a format modelled on EPD. Every line holds a position in FEN,
+/// followed by operations ending in semicolons:
+///
+/// ```text
+/// B:W12,16,18,19,20:B3,4,8,14,K23,25 bm 14-17; id "shot.001";
+/// ```
+///
+/// `bm` lists one or more best plies, written like [`Ply::notation`] in the numbering of
+/// the FEN. Captures may also be given with just their first and last square. `id` names
+/// the position and defaults to its line number. Other operations are ignored, as are
+/// empty lines and lines starting with `#`.
+pub fn parse_suite(text: &str) -> Result<Vec<TestPosition>, SuiteError> {
+    let mut positions = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let (number, line) = (index + 1, line.trim());
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (fen, operations) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let board = Board::from_fen(fen).map_err(|err| SuiteError::InvalidFen(number, err))?;
+
+        let mut id = number.to_string();
+        let mut best = Vec::new();
+        for operation in operations.split(';').map(str::trim).filter(|operation| !operation.is_empty()) {
+            let invalid = || SuiteError::InvalidOperation(number, operation.to_string());
+            let (opcode, operands) = operation.split_once(char::is_whitespace).ok_or_else(invalid)?;
+            match opcode {
+                "bm" => {
+                    for token in operands.split_whitespace() {
+                        let ply =
+                            find_ply(&board, token).ok_or_else(|| SuiteError::IllegalMove(number, token.into()))?;
+                        best.push(ply);
+                    }
+                }
+                "id" => {
+                    let operands = operands.trim();
+                    let quoted = operands.strip_prefix('"').and_then(|rest| rest.strip_suffix('"'));
+                    id = quoted.ok_or_else(invalid)?.to_string();
+                }
+                _ => {}
+            }
+        }
+        if best.is_empty() {
+            return Err(SuiteError::MissingBestMove(number));
+        }
+        positions.push(TestPosition { id, board, best });
+    }
+    Ok(positions)
+}
+
+/// Returns the regression suite shipped with the crate: positions from engine games
+/// where a forced sequence of captures wins material, which the engine has to keep finding.
+pub fn regression_suite() -> Vec<TestPosition> {
+    parse_suite(REGRESSION).expect("the shipped regression suite is valid")
+}
+
+/// The outcome of one position of a test suite.
+pub struct SuiteResult<'a> {
+    pub position: &'a TestPosition,
+    pub result: SearchResult,
+    pub solved: bool,
+}
+
+/// Searches every position of the suite with the engine's options, e.g. a fixed time per
+/// move, and hands each outcome to `on_position`. Returns the number of solved positions.
+/// The transposition table is cleared before each position, so they do not help each other.
+pub fn run_suite<F>(engine: &mut Engine, positions: &[TestPosition], mut on_position: F) -> usize
+where
+    F: FnMut(&SuiteResult),
+{
+    let mut solved = 0;
+    for position in positions {
+        engine.clear();
+        let result = engine.think(&position.board);
+        let outcome =
+            SuiteResult { position, solved: result.best_ply().is_some_and(|ply| position.best.contains(ply)), result };
+        solved += usize::from(outcome.solved);
+        on_position(&outcome);
+    }
+    solved
+}
+
+fn find_ply(board: &Board, token: &str) -> Option<Ply> {
+    let squares = token
+        .split(['-', 'x'])
+        .map(|number| number.parse().ok().and_then(Square::from_number))
+        .collect::<Option<Vec<Square>>>()?;
+    let (first, last) = (*squares.first()?, *squares.last()?);
+
+    board.get_backend().get_legal_plies(board.to_move()).into_iter().find(|ply| {
+        let path = ply.path();
+        ply.from() == first && path.last() == Some(&last) && (squares.len() == 2 || path == squares)
+    })
+}
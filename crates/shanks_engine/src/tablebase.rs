@@ -0,0 +1,110 @@
+mod compressed;
+mod generate;
+mod index;
+mod slice;
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+use shanks_core::board::{Backend, Color};
+
+pub use index::Material;
+pub use slice::{Outcome, TableSlice};
+
+/// Perfect knowledge about endgames with few pieces on the board.
+///
+/// The tablebase is split into slices, one per [`Material`], which store the outcome of
+/// every position with that material for both sides to move.
+#[derive(Default)]
+pub struct Tablebase {
+    slices: HashMap<Material, TableSlice>,
+    max_pieces: u8,
+}
+
+impl Tablebase {
+    /// Solves all endgames with at most `max_pieces` pieces on the board.
+    /// `progress` is called with every slice once it has been generated.
+    pub fn generate(max_pieces: u8, mut progress: impl FnMut(&TableSlice)) -> Self {
+        let mut slices = HashMap::new();
+        for material in Material::all(max_pieces) {
+            let slice = generate::generate(material, &slices);
+            progress(&slice);
+            slices.insert(material, slice);
+        }
+        Self::from_slices(slices)
+    }
+
+    /// Loads every slice stored in the directory. Compressed slices are memory-mapped
+    /// and take precedence over uncompressed ones of the same material.
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut slices = HashMap::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let slice = match path.extension().and_then(|extension| extension.to_str()) {
+                Some(slice::COMPRESSED_EXTENSION) => TableSlice::open_compressed(&path)?,
+                Some(slice::EXTENSION) => TableSlice::load(&path)?,
+                _ => continue,
+            };
+            let material = slice.material();
+            if slices.get(&material).is_none_or(|existing: &TableSlice| !existing.is_compressed()) {
+                slices.insert(material, slice);
+            }
+        }
+        Ok(Self::from_slices(slices))
+    }
+
+    fn from_slices(slices: HashMap<Material, TableSlice>) -> Self {
+        let max_pieces = slices.keys().map(Material::pieces).max().unwrap_or(0);
+        Self { slices, max_pieces }
+    }
+
+    /// Stores every slice in its own file in the directory, which is created if necessary.
+    pub fn save(&self, dir: impl AsRef<Path>, compressed: bool) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (material, slice) in &self.slices {
+            let path = dir.join(TableSlice::file_name(*material, compressed));
+            if compressed {
+                slice.save_compressed(path)?;
+            } else {
+                slice.save(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the slice of the material, if it is in the tablebase.
+    pub fn slice(&self, material: Material) -> Option<&TableSlice> {
+        self.slices.get(&material)
+    }
+
+    /// Returns the largest number of pieces of a position in the tablebase.
+    pub fn max_pieces(&self) -> u8 {
+        self.max_pieces
+    }
+
+    pub fn len(&self) -> usize {
+        self.slices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slices.is_empty()
+    }
+
+    pub fn slices(&self) -> impl Iterator<Item = &TableSlice> {
+        self.slices.values()
+    }
+
+    /// Returns the outcome of the position for `color`, the side to move,
+    /// or `None` if its material is not in the tablebase.
+    pub fn probe(&self, backend: &dyn Backend, color: Color) -> Option<Outcome> {
+        let material = Material::of(backend);
+        if material.count(color) == 0 {
+            return Some(Outcome::Loss(0));
+        }
+        if material.pieces() > self.max_pieces {
+            return None;
+        }
+        let slice = self.slices.get(&material)?;
+        slice.get(material.index(backend)?, color)
+    }
+}
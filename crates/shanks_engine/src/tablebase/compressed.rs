@@ -0,0 +1,372 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use memmap2::Mmap;
+use shanks_core::board::Color;
+
+use super::{slice::ILLEGAL, Material};
+
+const MAGIC: &[u8; 4] = b"SHTC";
+const VERSION: u32 = 2;
+const HEADER_SIZE: usize = 32;
+/// Number of entries per block. Larger blocks waste fewer bits on padding and offsets but
+/// take longer to probe.
+const BLOCK_SIZE: usize = 256;
+/// Number of different entry values.
+const SYMBOLS: usize = 256;
+/// The longest code the decoder accepts. Huffman codes only get this long for tables far
+/// larger than any slice.
+const MAX_CODE_LENGTH: u32 = 48;
+/// Codes up to this length are decoded by a single table lookup.
+const LOOKUP_BITS: u32 = 8;
+
+/// The entries of a slice, Huffman coded in blocks and memory-mapped from a file.
+///
+/// The file starts with the magic bytes `SHTC`, a little-endian `u32` version, the four
+/// piece counts of the material padded to 8 bytes, the number of entries per side as a
+/// `u64`, the number of entries per block and the number of blocks per side as `u32`s.
+/// It is followed by the lengths of the canonical Huffman codes of all 256 entry values,
+/// a table of `u64` offsets of the blocks, first with white and then with black to move,
+/// plus the end of the last block, and then the blocks themselves.
+///
+/// Every block starts on a byte boundary and holds the codes of its entries, most
+/// significant bit first. Probing a position only decodes the entries of its own block.
+pub(super) struct CompressedEntries {
+    material: Material,
+    map: Mmap,
+    blocks: usize,
+    decoder: Decoder,
+}
+
+impl CompressedEntries {
+    pub(super) fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: tablebase files are written once and never modified while they are in use.
+        let map = unsafe { Mmap::map(&file)? };
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+        if map.len() < HEADER_SIZE + SYMBOLS || &map[..4] != MAGIC {
+            return Err(invalid("not a compressed tablebase file"));
+        }
+        if read_u32(&map, 4) != VERSION {
+            return Err(invalid("unsupported tablebase version"));
+        }
+        let material = Material::new(map[8], map[9], map[10], map[11]);
+        if !material.is_legal() {
+            return Err(invalid("the material is not legal"));
+        }
+        let blocks = read_u32(&map, 28) as usize;
+        if read_u64(&map, 16) != material.size()
+            || read_u32(&map, 24) as usize != BLOCK_SIZE
+            || blocks != (material.size() as usize).div_ceil(BLOCK_SIZE)
+        {
+            return Err(invalid("the header does not match the material"));
+        }
+        let decoder = Decoder::new(&map[HEADER_SIZE..HEADER_SIZE + SYMBOLS]).ok_or_else(|| invalid("invalid codes"))?;
+
+        let entries = Self { material, map, blocks, decoder };
+        if entries.map.len() < entries.data_start() {
+            return Err(invalid("the file is truncated"));
+        }
+        let data = entries.map.len() - entries.data_start();
+        if (0..2 * blocks).any(|block| entries.offset(block) > entries.offset(block + 1)) {
+            return Err(invalid("the block offsets are not in order"));
+        }
+        if entries.offset(2 * blocks) > data {
+            return Err(invalid("the file is truncated"));
+        }
+        Ok(entries)
+    }
+
+    pub(super) fn material(&self) -> Material {
+        self.material
+    }
+
+    /// Returns the entry of the position with the given index.
+    pub(super) fn entry(&self, index: u64, color: Color) -> u8 {
+        if index >= self.material.size() {
+            return ILLEGAL;
+        }
+        let mut bits = BitReader::new(self.block(color as usize * self.blocks + index as usize / BLOCK_SIZE));
+        for _ in 0..index as usize % BLOCK_SIZE {
+            self.decoder.decode(&mut bits);
+        }
+        self.decoder.decode(&mut bits).unwrap_or(ILLEGAL)
+    }
+
+    /// Decodes all entries of one side to move.
+    pub(super) fn decode(&self, color: Color) -> Vec<u8> {
+        let size = self.material.size() as usize;
+        let mut entries = Vec::with_capacity(size);
+        for block in 0..self.blocks {
+            let mut bits = BitReader::new(self.block(color as usize * self.blocks + block));
+            for _ in 0..BLOCK_SIZE.min(size - block * BLOCK_SIZE) {
+                entries.push(self.decoder.decode(&mut bits).unwrap_or(ILLEGAL));
+            }
+        }
+        entries
+    }
+
+    fn block(&self, block: usize) -> &[u8] {
+        let data = &self.map[self.data_start()..];
+        &data[self.offset(block)..self.offset(block + 1)]
+    }
+
+    fn data_start(&self) -> usize {
+        HEADER_SIZE + SYMBOLS + 8 * (2 * self.blocks + 1)
+    }
+
+    fn offset(&self, block: usize) -> usize {
+        read_u64(&self.map, HEADER_SIZE + SYMBOLS + 8 * block) as usize
+    }
+}
+
+/// Writes the entries of a slice in the compressed format.
+pub(super) fn write(path: impl AsRef<Path>, material: Material, entries: &[Vec<u8>; 2]) -> io::Result<()> {
+    let mut frequencies = [0u64; SYMBOLS];
+    for &entry in entries.iter().flatten() {
+        frequencies[entry as usize] += 1;
+    }
+    let lengths = code_lengths(&frequencies);
+    let codes = canonical_codes(&lengths);
+
+    let size = material.size() as usize;
+    let blocks = size.div_ceil(BLOCK_SIZE);
+    let mut offsets = vec![0u64];
+    let mut data = Vec::new();
+    for side in entries {
+        for block in side.chunks(BLOCK_SIZE) {
+            let mut bits = BitWriter::new(&mut data);
+            for &entry in block {
+                bits.write(codes[entry as usize], lengths[entry as usize]);
+            }
+            bits.finish();
+            offsets.push(data.len() as u64);
+        }
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[material.white_men, material.white_kings, material.black_men, material.black_kings])?;
+    writer.write_all(&[0; 4])?;
+    writer.write_all(&(size as u64).to_le_bytes())?;
+    writer.write_all(&(BLOCK_SIZE as u32).to_le_bytes())?;
+    writer.write_all(&(blocks as u32).to_le_bytes())?;
+    writer.write_all(&lengths)?;
+    for offset in offsets {
+        writer.write_all(&offset.to_le_bytes())?;
+    }
+    writer.write_all(&data)?;
+    writer.flush()
+}
+
+/// Returns the lengths of the Huffman codes of the entry values, 0 for unused values.
+fn code_lengths(frequencies: &[u64; SYMBOLS]) -> [u8; SYMBOLS] {
+    let mut lengths = [0; SYMBOLS];
+    let used: Vec<usize> = (0..SYMBOLS).filter(|&symbol| frequencies[symbol] > 0).collect();
+    if used.len() == 1 {
+        lengths[used[0]] = 1;
+        return lengths;
+    }
+
+    // Nodes 0..SYMBOLS are the leaves, every merge adds an inner node.
+    let mut parents = vec![usize::MAX; SYMBOLS];
+    let mut heap: BinaryHeap<Reverse<(u64, usize)>> =
+        used.iter().map(|&symbol| Reverse((frequencies[symbol], symbol))).collect();
+    while heap.len() > 1 {
+        let Reverse((first, a)) = heap.pop().expect("two nodes are left");
+        let Reverse((second, b)) = heap.pop().expect("two nodes are left");
+        let node = parents.len();
+        parents.push(usize::MAX);
+        parents[a] = node;
+        parents[b] = node;
+        heap.push(Reverse((first + second, node)));
+    }
+
+    for &symbol in &used {
+        let mut node = symbol;
+        while parents[node] != usize::MAX {
+            lengths[symbol] += 1;
+            node = parents[node];
+        }
+        assert!(lengths[symbol] as u32 <= MAX_CODE_LENGTH, "the Huffman code is too long");
+    }
+    lengths
+}
+
+/// Assigns canonical codes: shorter codes first, codes of the same length in symbol order.
+fn canonical_codes(lengths: &[u8; SYMBOLS]) -> [u64; SYMBOLS] {
+    let mut codes = [0; SYMBOLS];
+    let mut code = 0u64;
+    let mut previous = 0;
+    for (symbol, length) in canonical_order(lengths) {
+        code <<= length - previous;
+        codes[symbol] = code;
+        code += 1;
+        previous = length;
+    }
+    codes
+}
+
+/// Returns the used symbols with their code lengths, sorted by length and symbol.
+fn canonical_order(lengths: &[u8; SYMBOLS]) -> Vec<(usize, u8)> {
+    let mut order: Vec<(usize, u8)> = lengths
+        .iter()
+        .enumerate()
+        .filter(|(_, &length)| length > 0)
+        .map(|(symbol, &length)| (symbol, length))
+        .collect();
+    order.sort_by_key(|&(symbol, length)| (length, symbol));
+    order
+}
+
+/// Decodes canonical Huffman codes, the short ones by a table lookup and the others one bit
+/// at a time.
+struct Decoder {
+    /// The symbol and code length for every `LOOKUP_BITS` bits starting with a code of at
+    /// most that length, and a length of 0 for the others.
+    lookup: [(u8, u8); 1 << LOOKUP_BITS],
+    /// The first code of each length.
+    first_code: [u64; MAX_CODE_LENGTH as usize + 1],
+    /// The index of the first symbol of each length in `symbols`.
+    first_symbol: [usize; MAX_CODE_LENGTH as usize + 1],
+    count: [u64; MAX_CODE_LENGTH as usize + 1],
+    symbols: Vec<u8>,
+}
+
+impl Decoder {
+    fn new(lengths: &[u8]) -> Option<Self> {
+        let lengths: &[u8; SYMBOLS] = lengths.try_into().ok()?;
+        if lengths.iter().any(|&length| length as u32 > MAX_CODE_LENGTH) {
+            return None;
+        }
+        // The codes of a prefix code cover at most the whole code space (Kraft's inequality).
+        let space: u64 =
+            lengths.iter().filter(|&&length| length > 0).map(|&length| 1 << (MAX_CODE_LENGTH - length as u32)).sum();
+        if space > 1 << MAX_CODE_LENGTH {
+            return None;
+        }
+        let mut decoder = Self {
+            lookup: [(0, 0); 1 << LOOKUP_BITS],
+            first_code: [0; MAX_CODE_LENGTH as usize + 1],
+            first_symbol: [0; MAX_CODE_LENGTH as usize + 1],
+            count: [0; MAX_CODE_LENGTH as usize + 1],
+            symbols: Vec::new(),
+        };
+        let codes = canonical_codes(lengths);
+        for (symbol, length) in canonical_order(lengths) {
+            let length = length as usize;
+            if decoder.count[length] == 0 {
+                decoder.first_code[length] = codes[symbol];
+                decoder.first_symbol[length] = decoder.symbols.len();
+            }
+            decoder.count[length] += 1;
+            decoder.symbols.push(symbol as u8);
+            if length as u32 <= LOOKUP_BITS {
+                let first = (codes[symbol] << (LOOKUP_BITS - length as u32)) as usize;
+                let span = 1 << (LOOKUP_BITS - length as u32);
+                decoder.lookup.get_mut(first..first + span)?.fill((symbol as u8, length as u8));
+            }
+        }
+        Some(decoder)
+    }
+
+    /// Returns the next symbol, or `None` at the end of the block.
+    fn decode(&self, bits: &mut BitReader) -> Option<u8> {
+        let (symbol, length) = self.lookup[bits.peek()];
+        if length > 0 {
+            return bits.skip(length as usize).then_some(symbol);
+        }
+        let mut code = 0;
+        for length in 1..=MAX_CODE_LENGTH as usize {
+            code = code << 1 | bits.read()?;
+            if self.count[length] > 0
+                && code >= self.first_code[length]
+                && code - self.first_code[length] < self.count[length]
+            {
+                return Some(self.symbols[self.first_symbol[length] + (code - self.first_code[length]) as usize]);
+            }
+        }
+        None
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read(&mut self) -> Option<u64> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1;
+        self.position += 1;
+        Some(bit as u64)
+    }
+
+    /// Returns the next `LOOKUP_BITS` bits without reading them, padded with zeros at the end.
+    fn peek(&self) -> usize {
+        let byte = |at: usize| self.bytes.get(at).copied().unwrap_or(0) as u16;
+        let at = self.position / 8;
+        let window = (byte(at) << 8 | byte(at + 1)) << (self.position % 8);
+        (window >> (16 - LOOKUP_BITS)) as usize
+    }
+
+    /// Skips `count` bits, returning whether there were that many left.
+    fn skip(&mut self, count: usize) -> bool {
+        if self.position + count > self.bytes.len() * 8 {
+            return false;
+        }
+        self.position += count;
+        true
+    }
+}
+
+struct BitWriter<'a> {
+    bytes: &'a mut Vec<u8>,
+    buffer: u8,
+    filled: u32,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(bytes: &'a mut Vec<u8>) -> Self {
+        Self { bytes, buffer: 0, filled: 0 }
+    }
+
+    fn write(&mut self, code: u64, length: u8) {
+        for i in (0..length).rev() {
+            self.buffer = self.buffer << 1 | ((code >> i) & 1) as u8;
+            self.filled += 1;
+            if self.filled == 8 {
+                self.bytes.push(self.buffer);
+                self.buffer = 0;
+                self.filled = 0;
+            }
+        }
+    }
+
+    /// Pads the last byte with zeros.
+    fn finish(self) {
+        if self.filled > 0 {
+            self.bytes.push(self.buffer << (8 - self.filled));
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().expect("4 bytes"))
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().expect("8 bytes"))
+}
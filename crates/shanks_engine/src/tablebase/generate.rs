@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use shanks_core::board::{Board, Color, Piece, PieceKind, Square};
+
+use super::{
+    slice::{Outcome, TableSlice, DRAW, ILLEGAL},
+    Material,
+};
+
+/// Marks positions that cannot be lost, because a ply leaving the slice draws or wins.
+const NOT_LOST: u8 = u8::MAX;
+
+/// A position waiting in the queue of its distance to the end of the game.
+struct Pending {
+    index: u32,
+    color: Color,
+    /// Whether the side to move wins, otherwise it loses.
+    won: bool,
+    /// A win by a ply leaving the slice, which only holds if the position has not been
+    /// won faster by a ply within the slice in the meantime. All other queued positions
+    /// are solved when they are queued.
+    exit: bool,
+}
+
+/// What a position leads to, found by expanding it once.
+#[derive(Default)]
+struct Expansion {
+    /// The number of legal plies.
+    plies: usize,
+    /// The number of plies within the slice.
+    inside: u8,
+    /// The shortest loss for the opponent after a ply leaving the slice.
+    shortest_loss: Option<u8>,
+    /// One more than the longest win for the opponent after a ply leaving the slice.
+    longest_win: u8,
+    /// Whether a ply leaving the slice leads to a draw.
+    drawn: bool,
+}
+
+impl Expansion {
+    fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Loss(distance) => {
+                self.shortest_loss = Some(self.shortest_loss.map_or(distance, |loss| loss.min(distance)));
+            }
+            Outcome::Win(distance) => self.longest_win = self.longest_win.max(distance + 1),
+            Outcome::Draw => self.drawn = true,
+        }
+    }
+}
+
+/// Solves all positions with the given material by retrograde analysis.
+///
+/// First every position is expanded once. Plies that capture or promote leave the slice
+/// and lead to solved positions, plies within the slice are only counted. Positions without
+/// a legal ply are lost, the others are queued by the distance known so far. Then the
+/// positions are taken from the queue in the order of their distance to the end of the
+/// game and their predecessors within the slice are generated by taking back a ply: a
+/// predecessor of a lost position is won one ply later, and a predecessor of a won position
+/// is lost once none of its plies is left unsolved and none of them leaves the slice for a
+/// draw or a win. Positions never solved this way are draws.
+///
+/// All materials reachable by captures and promotions have to be in `solved`.
+pub(super) fn generate(material: Material, solved: &HashMap<Material, TableSlice>) -> TableSlice {
+    let size = material.size();
+    assert!(size <= u32::MAX as u64, "the slice {} is too large to be generated", material);
+
+    let mut entries = [vec![ILLEGAL; size as usize], vec![ILLEGAL; size as usize]];
+    // The number of plies within the slice not yet known to lead to a win for the opponent.
+    let mut unsolved = [vec![0u8; size as usize], vec![0u8; size as usize]];
+    // One more than the longest win of the opponent after a ply leaving the slice, or `NOT_LOST`.
+    let mut exits = [vec![0u8; size as usize], vec![0u8; size as usize]];
+    let mut queue: Vec<Vec<Pending>> = Vec::new();
+    let push = |queue: &mut Vec<Vec<Pending>>, distance: usize, pending: Pending| {
+        if queue.len() <= distance {
+            queue.resize_with(distance + 1, Vec::new);
+        }
+        queue[distance].push(pending);
+    };
+
+    for color in [Color::White, Color::Black] {
+        let side = color as usize;
+        for index in 0..size {
+            let Some(pieces) = material.position(index) else {
+                continue;
+            };
+            entries[side][index as usize] = DRAW;
+
+            let expansion = expand(material, solved, &pieces, color);
+            let Expansion { plies, inside, shortest_loss, longest_win, drawn } = expansion;
+            unsolved[side][index as usize] = inside;
+            let only_wins = shortest_loss.is_none() && !drawn;
+            exits[side][index as usize] = if only_wins { longest_win } else { NOT_LOST };
+            let pending = |won, exit| Pending { index: index as u32, color, won, exit };
+            if let Some(loss) = shortest_loss {
+                push(&mut queue, loss as usize + 1, pending(true, true));
+            } else if plies == 0 {
+                entries[side][index as usize] = Outcome::Loss(0).encode();
+                push(&mut queue, 0, pending(false, false));
+            } else if inside == 0 && only_wins {
+                entries[side][index as usize] = Outcome::Loss(longest_win).encode();
+                push(&mut queue, longest_win as usize, pending(false, false));
+            }
+        }
+    }
+
+    let mut distance = 0;
+    while distance < queue.len() {
+        for pending in std::mem::take(&mut queue[distance]) {
+            let (side, index) = (pending.color as usize, pending.index as usize);
+            if pending.exit {
+                if entries[side][index] != DRAW {
+                    continue;
+                }
+                entries[side][index] = Outcome::Win(stored(distance)).encode();
+            }
+
+            let pieces = material.position(index as u64).expect("queued positions are legal");
+            let mover = pending.color.opposite();
+            let opponent = mover as usize;
+            for predecessor in predecessors(material, &pieces, mover) {
+                let predecessor = predecessor as usize;
+                if entries[opponent][predecessor] != DRAW {
+                    continue;
+                }
+                let solved = if !pending.won {
+                    Some((true, distance + 1))
+                } else {
+                    unsolved[opponent][predecessor] -= 1;
+                    let exit = exits[opponent][predecessor];
+                    (unsolved[opponent][predecessor] == 0 && exit != NOT_LOST)
+                        .then(|| (false, (distance + 1).max(exit as usize)))
+                };
+                if let Some((won, at)) = solved {
+                    let outcome = if won { Outcome::Win(stored(at)) } else { Outcome::Loss(stored(at)) };
+                    entries[opponent][predecessor] = outcome.encode();
+                    let pending = Pending { index: predecessor as u32, color: mover, won, exit: false };
+                    push(&mut queue, at, pending);
+                }
+            }
+        }
+        distance += 1;
+    }
+
+    TableSlice::new(material, entries)
+}
+
+/// Counts the plies of the position with `color` to move that stay within the slice and
+/// looks up the outcomes of the plies that leave it.
+///
+/// Without a capture, the position is expanded on its own board, since then every ply is a
+/// single step and only promotions leave the slice. Captures are left to the backend.
+fn expand(
+    material: Material,
+    solved: &HashMap<Material, TableSlice>,
+    pieces: &[(Square, Piece)],
+    color: Color,
+) -> Expansion {
+    let mut expansion = Expansion::default();
+    let mut board = squares(pieces);
+    if can_capture(&board, color) {
+        let board = Board::from_pieces(color, pieces);
+        for ply in board.get_backend().get_legal_plies(color) {
+            let mut next = board.get_backend().clone_box();
+            next.ply(ply);
+            expansion.plies += 1;
+            expansion.add(exit(solved, Material::of(next.as_ref()), color.opposite(), |square| next.get_piece(square)));
+        }
+        return expansion;
+    }
+
+    let king = Piece::new(color, PieceKind::King);
+    let promoted = match color {
+        Color::White => Material {
+            white_men: material.white_men.saturating_sub(1),
+            white_kings: material.white_kings + 1,
+            ..material
+        },
+        Color::Black => Material {
+            black_men: material.black_men.saturating_sub(1),
+            black_kings: material.black_kings + 1,
+            ..material
+        },
+    };
+    for &(square, piece) in pieces.iter().filter(|(_, piece)| piece.color() == color) {
+        for (file, rank) in piece.moves() {
+            let Some(target) = square.moved_by(file, rank).filter(|target| board[target.index()].is_none()) else {
+                continue;
+            };
+            expansion.plies += 1;
+            if piece.is_king() || target.rank() != piece.promotion_rank() {
+                expansion.inside += 1;
+                continue;
+            }
+            board[square.index()] = None;
+            board[target.index()] = Some(king);
+            expansion.add(exit(solved, promoted, color.opposite(), |square| board[square.index()]));
+            board[target.index()] = None;
+            board[square.index()] = Some(piece);
+        }
+    }
+    expansion
+}
+
+/// Returns the outcome for `color`, the side to move, of a position after a ply that left
+/// the slice.
+fn exit(
+    solved: &HashMap<Material, TableSlice>,
+    material: Material,
+    color: Color,
+    piece_at: impl Fn(Square) -> Option<Piece>,
+) -> Outcome {
+    if material.count(color) == 0 {
+        return Outcome::Loss(0);
+    }
+    let slice = solved.get(&material).expect("captures and promotions lead to solved slices");
+    let index = material.index_of(piece_at).expect("the index matches the material");
+    slice.get(index, color).expect("positions after a ply are legal")
+}
+
+/// Returns the piece on every square of the board, by the index of the square.
+fn squares(pieces: &[(Square, Piece)]) -> [Option<Piece>; 64] {
+    let mut board = [None; 64];
+    for &(square, piece) in pieces {
+        board[square.index()] = Some(piece);
+    }
+    board
+}
+
+/// Returns the indices of the positions with `mover` to move that reach the given position
+/// by a ply within the slice, a step that neither captures nor promotes.
+fn predecessors(material: Material, pieces: &[(Square, Piece)], mover: Color) -> Vec<u32> {
+    let mut board = squares(pieces);
+    let mut predecessors = Vec::new();
+    for &(square, piece) in pieces {
+        if piece.color() != mover {
+            continue;
+        }
+        for (file, rank) in piece.moves() {
+            let Some(origin) = square.moved_by(-file, -rank) else {
+                continue;
+            };
+            if board[origin.index()].is_some() {
+                continue;
+            }
+            board[square.index()] = None;
+            board[origin.index()] = Some(piece);
+            // Captures are compulsory, so the step was only legal if there was nothing to capture.
+            if !can_capture(&board, mover) {
+                if let Some(index) = material.index_of(|square| board[square.index()]) {
+                    predecessors.push(index as u32);
+                }
+            }
+            board[origin.index()] = None;
+            board[square.index()] = Some(piece);
+        }
+    }
+    predecessors
+}
+
+/// Returns whether a piece of `color` can capture on the board, given as the piece on
+/// every square by index.
+fn can_capture(board: &[Option<Piece>; 64], color: Color) -> bool {
+    Square::ALL.into_iter().any(|square| {
+        let Some(piece) = board[square.index()].filter(|piece| piece.color() == color) else {
+            return false;
+        };
+        piece.moves().into_iter().any(|(file, rank)| {
+            let Some(target) = square.moved_by(file, rank) else {
+                return false;
+            };
+            board[target.index()].is_some_and(|target| target.color() != color)
+                && target.moved_by(file, rank).is_some_and(|landing| board[landing.index()].is_none())
+        })
+    })
+}
+
+fn stored(distance: usize) -> u8 {
+    distance.min(u8::MAX as usize) as u8
+}
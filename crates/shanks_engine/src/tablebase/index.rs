@@ -0,0 +1,233 @@
+use std::fmt;
+
+use shanks_core::board::{Backend, Color, Piece, Square};
+
+/// Number of squares a man can stand on, men are promoted as soon as they reach the last rank.
+const MAN_SQUARES: usize = 28;
+
+/// `BINOMIAL[n][k]` is the number of ways to choose `k` of `n` squares.
+const BINOMIAL: [[u64; 33]; 33] = binomials();
+
+const fn binomials() -> [[u64; 33]; 33] {
+    let mut table = [[0; 33]; 33];
+    let mut n = 0;
+    while n < 33 {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+}
+
+/// The pieces left on the board, which selects the slice of the tablebase a position belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Material {
+    pub white_men: u8,
+    pub white_kings: u8,
+    pub black_men: u8,
+    pub black_kings: u8,
+}
+
+impl Material {
+    pub fn new(white_men: u8, white_kings: u8, black_men: u8, black_kings: u8) -> Self {
+        Self { white_men, white_kings, black_men, black_kings }
+    }
+
+    pub fn of(backend: &dyn Backend) -> Self {
+        Self::new(
+            backend.man_count(Color::White),
+            backend.king_count(Color::White),
+            backend.man_count(Color::Black),
+            backend.king_count(Color::Black),
+        )
+    }
+
+    /// Returns all materials with at least one piece per side and at most `max_pieces` pieces,
+    /// in the order they have to be generated in.
+    ///
+    /// Captures lead to materials with fewer pieces and promotions to materials with fewer
+    /// men, so every material only depends on materials that come before it.
+    pub fn all(max_pieces: u8) -> Vec<Material> {
+        let mut materials = Vec::new();
+        for white_men in 0..=max_pieces {
+            for white_kings in 0..=max_pieces - white_men {
+                for black_men in 0..=max_pieces - white_men - white_kings {
+                    for black_kings in 0..=max_pieces - white_men - white_kings - black_men {
+                        let material = Material::new(white_men, white_kings, black_men, black_kings);
+                        if material.count(Color::White) > 0 && material.count(Color::Black) > 0 {
+                            materials.push(material);
+                        }
+                    }
+                }
+            }
+        }
+        materials.sort_by_key(|material| (material.pieces(), material.men(), *material));
+        materials
+    }
+
+    /// Returns the total number of pieces.
+    pub fn pieces(&self) -> u8 {
+        self.white_men + self.white_kings + self.black_men + self.black_kings
+    }
+
+    /// Returns the total number of men.
+    pub fn men(&self) -> u8 {
+        self.white_men + self.black_men
+    }
+
+    /// Returns the number of pieces of one side.
+    pub fn count(&self, color: Color) -> u8 {
+        match color {
+            Color::White => self.white_men + self.white_kings,
+            Color::Black => self.black_men + self.black_kings,
+        }
+    }
+
+    /// Returns whether a game can reach the material, with one to twelve pieces per side.
+    /// Only such materials have a [`Material::size`].
+    pub fn is_legal(&self) -> bool {
+        let legal = |men: u8, kings: u8| men <= 12 && kings <= 12 && (1..=12).contains(&(men + kings));
+        legal(self.white_men, self.white_kings) && legal(self.black_men, self.black_kings)
+    }
+
+    /// Returns the number of indices of the material, i.e. the number of entries per side to move.
+    ///
+    /// White and black men are placed independently of each other, so some indices
+    /// describe positions with two men on one square. [`Material::position`] rejects them.
+    pub fn size(&self) -> u64 {
+        let free = 32 - self.men() as usize;
+        BINOMIAL[MAN_SQUARES][self.white_men as usize]
+            * BINOMIAL[MAN_SQUARES][self.black_men as usize]
+            * BINOMIAL[free][self.white_kings as usize]
+            * BINOMIAL[free - self.white_kings as usize][self.black_kings as usize]
+    }
+
+    /// Returns the index of the position on the board, or `None` if it has a different material.
+    ///
+    /// The index combines the ranks of the four groups of pieces in the combinatorial number
+    /// system: white men among the squares 5-32, black men among the squares 1-28 and the
+    /// kings among the squares left free by the men.
+    pub fn index(&self, backend: &dyn Backend) -> Option<u64> {
+        self.index_of(|square| backend.get_piece(square))
+    }
+
+    /// Like [`Material::index`], with the pieces given by a function of the square.
+    pub(super) fn index_of(&self, piece_at: impl Fn(Square) -> Option<Piece>) -> Option<u64> {
+        let mut groups: [Vec<usize>; 4] = Default::default();
+        let mut free = 0;
+        for number in 1..=32 {
+            let square = Square::from_number(number).expect("squares are numbered 1-32");
+            match piece_at(square) {
+                // Men on their promotion rank cannot occur in a game.
+                Some(piece) if !piece.is_king() => match piece.color() {
+                    Color::White if number > 4 => groups[0].push(number as usize - 5),
+                    Color::Black if number <= 28 => groups[1].push(number as usize - 1),
+                    _ => return None,
+                },
+                Some(piece) => {
+                    let group = if piece.color() == Color::White { 2 } else { 3 };
+                    // Black kings are ranked among the squares left free by the white kings.
+                    let white_kings = if group == 3 { groups[2].len() } else { 0 };
+                    groups[group].push(free - white_kings);
+                    free += 1;
+                }
+                None => free += 1,
+            }
+        }
+
+        let counts = self.counts();
+        if groups.iter().zip(counts).any(|(group, count)| group.len() != count) {
+            return None;
+        }
+        let mut index = 0;
+        for (group, size) in groups.iter().zip(self.group_sizes()) {
+            index = index * BINOMIAL[size][group.len()] + rank(group);
+        }
+        Some(index)
+    }
+
+    /// Returns the pieces of the position with the given index, or `None` if the index
+    /// does not describe a valid position.
+    pub fn position(&self, mut index: u64) -> Option<Vec<(Square, Piece)>> {
+        if index >= self.size() {
+            return None;
+        }
+        let counts = self.counts();
+        let sizes = self.group_sizes();
+        let mut ranks = [0; 4];
+        for group in (0..4).rev() {
+            let combinations = BINOMIAL[sizes[group]][counts[group]];
+            ranks[group] = index % combinations;
+            index /= combinations;
+        }
+
+        let mut occupied = [None; 33];
+        for number in unrank(ranks[0], counts[0], MAN_SQUARES) {
+            occupied[number + 5] = Some(Piece::WHITE_MAN);
+        }
+        for number in unrank(ranks[1], counts[1], MAN_SQUARES) {
+            if occupied[number + 1].is_some() {
+                return None;
+            }
+            occupied[number + 1] = Some(Piece::BLACK_MAN);
+        }
+        for (group, piece) in [(2, Piece::WHITE_KING), (3, Piece::BLACK_KING)] {
+            let free: Vec<usize> = (1..=32).filter(|&number| occupied[number].is_none()).collect();
+            for position in unrank(ranks[group], counts[group], sizes[group]) {
+                occupied[free[position]] = Some(piece);
+            }
+        }
+
+        Some(
+            (1..=32)
+                .filter_map(|number| {
+                    let square = Square::from_number(number as u8)?;
+                    occupied[number].map(|piece| (square, piece))
+                })
+                .collect(),
+        )
+    }
+
+    /// The number of pieces in each group, in index order.
+    fn counts(&self) -> [usize; 4] {
+        [self.white_men, self.black_men, self.white_kings, self.black_kings].map(usize::from)
+    }
+
+    /// The number of squares each group is placed on, in index order.
+    fn group_sizes(&self) -> [usize; 4] {
+        let free = 32 - self.men() as usize;
+        [MAN_SQUARES, MAN_SQUARES, free, free - self.white_kings as usize]
+    }
+}
+
+/// Formats the material as e.g. `2m1k-0m2k`, white's pieces first.
+impl fmt::Display for Material {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}m{}k-{}m{}k", self.white_men, self.white_kings, self.black_men, self.black_kings)
+    }
+}
+
+/// Returns the rank of a set of ascending positions in the combinatorial number system.
+fn rank(positions: &[usize]) -> u64 {
+    positions.iter().enumerate().map(|(i, &position)| BINOMIAL[position][i + 1]).sum()
+}
+
+/// Returns the `count` ascending positions below `size` with the given rank.
+fn unrank(mut rank: u64, count: usize, size: usize) -> Vec<usize> {
+    let mut positions = vec![0; count];
+    let mut limit = size;
+    for i in (0..count).rev() {
+        let mut position = limit - 1;
+        while BINOMIAL[position][i + 1] > rank {
+            position -= 1;
+        }
+        rank -= BINOMIAL[position][i + 1];
+        positions[i] = position;
+        limit = position;
+    }
+    positions
+}
@@ -0,0 +1,224 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use shanks_core::board::Color;
+
+use super::{
+    compressed::{self, CompressedEntries},
+    Material,
+};
+
+const MAGIC: &[u8; 4] = b"SHTB";
+const VERSION: u32 = 1;
+pub(super) const EXTENSION: &str = "stb";
+pub(super) const COMPRESSED_EXTENSION: &str = "stc";
+
+/// Entry of an index that does not describe a valid position.
+pub(super) const ILLEGAL: u8 = 0;
+/// Entry of a drawn position, and of every position that is not resolved yet during generation.
+pub(super) const DRAW: u8 = 1;
+/// The longest distance that can be stored, longer distances are stored as this one.
+const MAX_DISTANCE: u8 = 126;
+
+/// The game-theoretic value of a position from the point of view of the side to move.
+///
+/// Wins and losses carry the number of plies until the game ends with best play:
+/// the winner takes the shortest way and the loser the longest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win(u8),
+    Loss(u8),
+    Draw,
+}
+
+impl Outcome {
+    /// Returns the outcome of a table entry, or `None` for an illegal position.
+    pub(super) fn decode(entry: u8) -> Option<Outcome> {
+        match entry {
+            ILLEGAL => None,
+            DRAW => Some(Outcome::Draw),
+            _ if entry.is_multiple_of(2) => Some(Outcome::Loss((entry - 2) / 2)),
+            _ => Some(Outcome::Win((entry - 3) / 2)),
+        }
+    }
+
+    pub(super) fn encode(self) -> u8 {
+        match self {
+            Outcome::Draw => DRAW,
+            Outcome::Loss(distance) => 2 + 2 * distance.min(MAX_DISTANCE),
+            Outcome::Win(distance) => 3 + 2 * distance.min(MAX_DISTANCE),
+        }
+    }
+
+    /// Returns the distance to the end of the game, or `None` for a draw.
+    pub fn distance(&self) -> Option<u8> {
+        match *self {
+            Outcome::Win(distance) | Outcome::Loss(distance) => Some(distance),
+            Outcome::Draw => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Win(distance) => write!(f, "win in {} plies", distance),
+            Outcome::Loss(distance) => write!(f, "loss in {} plies", distance),
+            Outcome::Draw => write!(f, "draw"),
+        }
+    }
+}
+
+/// The outcomes of all positions with one material, for both sides to move.
+///
+/// A slice either holds its entries in memory, as generated or loaded from an uncompressed
+/// file, or probes a memory-mapped compressed file.
+pub struct TableSlice {
+    material: Material,
+    entries: Entries,
+}
+
+enum Entries {
+    Plain([Vec<u8>; 2]),
+    Compressed(Box<CompressedEntries>),
+}
+
+impl TableSlice {
+    pub(super) fn new(material: Material, entries: [Vec<u8>; 2]) -> Self {
+        Self { material, entries: Entries::Plain(entries) }
+    }
+
+    pub fn material(&self) -> Material {
+        self.material
+    }
+
+    /// Returns the outcome of the position with the given index, or `None` for an illegal index.
+    pub fn get(&self, index: u64, color: Color) -> Option<Outcome> {
+        Outcome::decode(self.entry(index, color))
+    }
+
+    pub(super) fn entry(&self, index: u64, color: Color) -> u8 {
+        match &self.entries {
+            Entries::Plain(entries) => entries[color as usize].get(index as usize).copied().unwrap_or(ILLEGAL),
+            Entries::Compressed(entries) => entries.entry(index, color),
+        }
+    }
+
+    /// Returns whether the slice probes a compressed file.
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.entries, Entries::Compressed(_))
+    }
+
+    /// Returns the entries of both sides to move, decompressing them if necessary.
+    fn decoded(&self) -> [Vec<u8>; 2] {
+        match &self.entries {
+            Entries::Plain(entries) => entries.clone(),
+            Entries::Compressed(entries) => [entries.decode(Color::White), entries.decode(Color::Black)],
+        }
+    }
+
+    /// Returns the number of won, lost and drawn positions.
+    pub fn counts(&self) -> (u64, u64, u64) {
+        let (mut wins, mut losses, mut draws) = (0, 0, 0);
+        for &entry in self.decoded().iter().flatten() {
+            match Outcome::decode(entry) {
+                Some(Outcome::Win(_)) => wins += 1,
+                Some(Outcome::Loss(_)) => losses += 1,
+                Some(Outcome::Draw) => draws += 1,
+                None => {}
+            }
+        }
+        (wins, losses, draws)
+    }
+
+    /// Returns the longest distance to a win or loss in the slice.
+    pub fn max_distance(&self) -> u8 {
+        self.decoded().iter().flatten().filter_map(|&entry| Outcome::decode(entry)?.distance()).max().unwrap_or(0)
+    }
+
+    /// Returns the number of legal positions of `reference` whose outcome differs in this slice.
+    pub fn mismatches(&self, reference: &TableSlice) -> u64 {
+        let entries = self.decoded();
+        let mut mismatches = 0;
+        for (side, reference) in reference.decoded().iter().enumerate() {
+            for (index, &expected) in reference.iter().enumerate() {
+                if expected != ILLEGAL && entries[side].get(index) != Some(&expected) {
+                    mismatches += 1;
+                }
+            }
+        }
+        mismatches
+    }
+
+    /// Returns the name of the file the slice is stored in, e.g. `1m1k-0m2k.stb`,
+    /// or `1m1k-0m2k.stc` if it is compressed.
+    pub fn file_name(material: Material, compressed: bool) -> String {
+        format!("{}.{}", material, if compressed { COMPRESSED_EXTENSION } else { EXTENSION })
+    }
+
+    /// Reads a slice written by [`TableSlice::save`].
+    ///
+    /// The file starts with the magic bytes `SHTB` and a little-endian `u32` version,
+    /// followed by the four piece counts of the material padded to 8 bytes, the number of
+    /// entries per side as a `u64` and then one byte per entry, first with white and then
+    /// with black to move.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+        let mut header = [0; 16];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not a tablebase file"));
+        }
+        if u32::from_le_bytes(header[4..8].try_into().expect("4 bytes")) != VERSION {
+            return Err(invalid("unsupported tablebase version"));
+        }
+        let material = Material::new(header[8], header[9], header[10], header[11]);
+        if !material.is_legal() {
+            return Err(invalid("the material is not legal"));
+        }
+        let mut size = [0; 8];
+        reader.read_exact(&mut size)?;
+        if u64::from_le_bytes(size) != material.size() {
+            return Err(invalid("the size does not match the material"));
+        }
+
+        let mut entries: [Vec<u8>; 2] = Default::default();
+        for entries in &mut entries {
+            *entries = vec![ILLEGAL; material.size() as usize];
+            reader.read_exact(entries)?;
+        }
+        Ok(Self::new(material, entries))
+    }
+
+    /// Opens a compressed file written by [`TableSlice::save_compressed`]. The file is
+    /// memory-mapped, so only the blocks that are probed are ever read.
+    pub fn open_compressed(path: impl AsRef<Path>) -> io::Result<Self> {
+        let entries = CompressedEntries::open(path)?;
+        Ok(Self { material: entries.material(), entries: Entries::Compressed(Box::new(entries)) })
+    }
+
+    /// Writes the slice in the compressed format, see [`TableSlice::open_compressed`].
+    pub fn save_compressed(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        compressed::write(path, self.material, &self.decoded())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let material = self.material;
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&[material.white_men, material.white_kings, material.black_men, material.black_kings])?;
+        writer.write_all(&[0; 4])?;
+        writer.write_all(&material.size().to_le_bytes())?;
+        for entries in &self.decoded() {
+            writer.write_all(entries)?;
+        }
+        writer.flush()
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
+
+use crate::MAN_VALUE;
+
+/// Time reserved per move for the overhead of communicating the move.
+const MOVE_OVERHEAD: Duration = Duration::from_millis(20);
+/// Number of moves the remaining time is budgeted for when no moves-to-go are given.
+const EXPECTED_MOVES: u32 = 30;
+/// The hard limit is at most this many times the soft limit.
+const MAX_EXTENSION: u32 = 4;
+/// A score drop of this much against the previous iteration extends the search.
+const SCORE_DROP: f64 = 0.3 * MAN_VALUE;
+
+/// The state of a player's clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Time left on the clock.
+    pub remaining: Duration,
+    /// Time added to the clock after every move.
+    pub increment: Duration,
+    /// Number of moves until the next time control, if the clock is not sudden death.
+    pub moves_to_go: Option<u32>,
+}
+
+/// How long a search may take.
+///
+/// The search ends at the hard limit in any case. It does not start another iteration
+/// once the soft limit is half used, since the next iteration usually takes longer than
+/// all previous ones together. The soft limit is extended while the best ply keeps
+/// changing or the score drops, up to the hard limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLimits {
+    pub soft: Duration,
+    pub hard: Duration,
+}
+
+impl TimeLimits {
+    /// Uses exactly `time` for the move, without extensions.
+    pub fn fixed(time: Duration) -> Self {
+        Self { soft: time, hard: time }
+    }
+
+    /// Budgets the time for one move: an even share of the remaining time plus most of
+    /// the increment, never more than a third of the remaining time.
+    pub fn from_control(control: &TimeControl) -> Self {
+        let available = control.remaining.saturating_sub(MOVE_OVERHEAD);
+        let moves = control.moves_to_go.unwrap_or(EXPECTED_MOVES).max(1);
+        let hard = (available / 3).min(available / moves * MAX_EXTENSION + control.increment);
+        let soft = (available / moves + control.increment * 3 / 4).min(hard);
+        Self { soft, hard }
+    }
+}
+
+/// Keeps track of the time of a running search.
+#[derive(Debug, Clone)]
+pub(crate) struct TimeManager {
+    limits: TimeLimits,
+    /// When the clock started. A ponder search runs without limits until it is set.
+    start: Arc<OnceLock<Instant>>,
+}
+
+impl TimeManager {
+    pub(crate) fn new(limits: TimeLimits) -> Self {
+        Self { limits, start: Arc::new(OnceLock::from(Instant::now())) }
+    }
+
+    /// Keeps the limits of a ponder search, which only apply from the time `start` is set,
+    /// once the opponent played the expected reply.
+    pub(crate) fn pondering(limits: TimeLimits, start: Arc<OnceLock<Instant>>) -> Self {
+        Self { limits, start }
+    }
+
+    /// Returns the time since the clock started, `None` while pondering.
+    fn elapsed(&self) -> Option<Duration> {
+        self.start.get().map(Instant::elapsed)
+    }
+
+    /// Returns `true` once the hard limit is reached.
+    pub(crate) fn out_of_time(&self) -> bool {
+        self.elapsed().is_some_and(|elapsed| elapsed >= self.limits.hard)
+    }
+
+    /// Returns `true` once the soft limit is reached. Used by searches without
+    /// iterations, which simply stop there.
+    pub(crate) fn past_soft_limit(&self) -> bool {
+        self.elapsed().is_some_and(|elapsed| elapsed >= self.limits.soft)
+    }
+
+    /// Decides after an iteration whether to start the next one. `changes` counts how
+    /// often the best ply changed over the last iterations.
+    pub(crate) fn next_iteration(&self, changes: f64, score: f64, previous: Option<f64>) -> bool {
+        let mut scale = 1.0 + changes / 2.0;
+        if previous.is_some_and(|previous| score < previous - SCORE_DROP) {
+            scale *= 1.5;
+        }
+        let soft = self.limits.soft.mul_f64(scale).min(self.limits.hard);
+        self.elapsed().is_none_or(|elapsed| elapsed < soft / 2)
+    }
+}
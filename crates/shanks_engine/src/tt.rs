@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use shanks_core::board::Color;
+
+/// Added to the hash of positions where black is to move, so both sides get distinct entries.
+const BLACK_TO_MOVE: u64 = 0x9e37_79b9_7f4a_7c15;
+/// Marks an entry without a best ply.
+const NO_PLY: u64 = 0xFF;
+/// Scores are stored as fixed-point numbers with this many steps per unit. The search
+/// rounds its evaluations to the same grid, so scores come back from the table unchanged.
+const SCORE_SCALE: f64 = 1024.0;
+
+/// Rounds a score to the nearest value the transposition table stores exactly.
+pub(crate) fn quantize(score: f64) -> f64 {
+    (score * SCORE_SCALE).round() / SCORE_SCALE
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// The score is exact.
+    Exact = 1,
+    /// The score is a lower bound (the search failed high).
+    Lower = 2,
+    /// The score is an upper bound (the search failed low).
+    Upper = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TTEntry {
+    pub key: u64,
+    pub depth: u8,
+    pub score: f64,
+    pub bound: Bound,
+    /// Index of the best ply in the list returned by `Backend::get_legal_plies`.
+    pub best: Option<u8>,
+}
+
+impl TTEntry {
+    /// Packs everything but the key into a single word: the score as a fixed-point `i32`
+    /// in the low 32 bits, followed by depth, bound and best ply.
+    fn pack(&self) -> u64 {
+        (self.score * SCORE_SCALE).round() as i32 as u32 as u64
+            | (self.depth as u64) << 32
+            | (self.bound as u64) << 40
+            | self.best.map_or(NO_PLY, u64::from) << 42
+    }
+
+    fn unpack(key: u64, data: u64) -> Option<Self> {
+        let bound = match (data >> 40) & 0b11 {
+            1 => Bound::Exact,
+            2 => Bound::Lower,
+            3 => Bound::Upper,
+            _ => return None,
+        };
+        let best = (data >> 42) & 0xFF;
+        Some(Self {
+            key,
+            depth: (data >> 32) as u8,
+            score: data as u32 as i32 as f64 / SCORE_SCALE,
+            bound,
+            best: (best != NO_PLY).then_some(best as u8),
+        })
+    }
+}
+
+/// A fixed size, always-replace transposition table that can be shared between threads.
+///
+/// Entries are stored lock-free as two words, the key xor-ed with the data and the data
+/// itself. A torn write by a concurrent thread leaves a key that no longer matches, so
+/// such entries are simply treated as missing.
+pub struct TranspositionTable {
+    entries: Vec<[AtomicU64; 2]>,
+}
+
+impl TranspositionTable {
+    pub fn new(size: usize) -> Self {
+        Self { entries: (0..size.max(1)).map(|_| [AtomicU64::new(0), AtomicU64::new(0)]).collect() }
+    }
+
+    /// Combines the state hash of a backend with the side to move.
+    pub fn key(state_hash: u64, color: Color) -> u64 {
+        match color {
+            Color::White => state_hash,
+            Color::Black => state_hash ^ BLACK_TO_MOVE,
+        }
+    }
+
+    pub fn probe(&self, key: u64) -> Option<TTEntry> {
+        let [check, data] = &self.entries[self.index(key)];
+        let data = data.load(Ordering::Relaxed);
+        if check.load(Ordering::Relaxed) ^ data != key {
+            return None;
+        }
+        TTEntry::unpack(key, data)
+    }
+
+    pub fn store(&self, entry: TTEntry) {
+        let data = entry.pack();
+        let [check, slot] = &self.entries[self.index(entry.key)];
+        check.store(entry.key ^ data, Ordering::Relaxed);
+        slot.store(data, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        for [check, data] in &self.entries {
+            check.store(0, Ordering::Relaxed);
+            data.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.entries.len() as u64) as usize
+    }
+}
@@ -0,0 +1,205 @@
+use std::{fs, io, path::Path};
+
+use shanks_core::board::{Backend, Board, Color};
+
+use crate::{
+    eval::{features, EvalParams, Evaluator, PositionalEvaluator, TERM_COUNT},
+    search::quiescence,
+};
+
+/// A quiet position from the training data, reduced to the difference of its
+/// evaluation features between white and black.
+struct Sample {
+    features: [f64; TERM_COUNT],
+    /// The result of the game from white's point of view: 1 for a win, 0.5 for a draw, 0 for a loss.
+    result: f64,
+}
+
+/// Tunes the weights of the [`PositionalEvaluator`] on labelled positions (Texel's method).
+///
+/// Every position is resolved to the quiet position at the end of its quiescence search.
+/// The tuner then minimizes the mean squared error between the game results and
+/// `sigmoid(k * score)` by gradient descent, which is cheap since the evaluation is
+/// linear in its weights.
+pub struct Tuner {
+    samples: Vec<Sample>,
+    k: f64,
+}
+
+impl Tuner {
+    /// Loads positions from a file with one `FEN;result` line per position. Results are
+    /// given from white's point of view as `1-0`, `0-1`, `1/2-1/2` or a number between 0 and 1.
+    /// The `FEN;score;result` lines written by `shanks datagen` are accepted as well.
+    pub fn load(path: impl AsRef<Path>, params: &EvalParams) -> io::Result<Self> {
+        let mut positions = Vec::new();
+        for line in fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+            // Self-play data has a search score between the FEN and the result, which is not needed here.
+            let (fen, result) = line
+                .split_once(';')
+                .zip(line.rsplit_once(';'))
+                .map(|((fen, _), (_, result))| (fen, result))
+                .ok_or_else(|| invalid(format!("expected FEN;result: {}", line)))?;
+            let board = Board::from_fen(fen).map_err(|err| invalid(format!("{}: {}", err, fen)))?;
+            let result = parse_result(result.trim()).ok_or_else(|| invalid(format!("invalid result: {}", result)))?;
+            positions.push((board, result));
+        }
+        Ok(Self::new(&positions, params))
+    }
+
+    pub fn new(positions: &[(Board, f64)], params: &EvalParams) -> Self {
+        let evaluator = PositionalEvaluator::new(*params);
+        let samples = positions
+            .iter()
+            .filter_map(|(board, result)| {
+                let leaf = quiet_position(&evaluator, board.get_backend(), board.to_move())?;
+                let white = features(leaf.as_ref(), Color::White);
+                let black = features(leaf.as_ref(), Color::Black);
+                Some(Sample { features: std::array::from_fn(|term| white[term] - black[term]), result: *result })
+            })
+            .collect();
+        Self { samples, k: 1.0 }
+    }
+
+    /// Returns the number of usable positions.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the scaling constant of the sigmoid.
+    pub fn k(&self) -> f64 {
+        self.k
+    }
+
+    /// Returns the mean squared error of the predicted results.
+    pub fn error(&self, params: &EvalParams) -> f64 {
+        let weights = params.weights();
+        let total: f64 = self
+            .samples
+            .iter()
+            .map(|sample| (sample.result - sigmoid(self.k * dot(&weights, &sample.features))).powi(2))
+            .sum();
+        total / self.samples.len().max(1) as f64
+    }
+
+    /// Chooses the scaling constant that minimizes the error for the given parameters,
+    /// so the tuning doesn't just rescale all weights.
+    pub fn fit_k(&mut self, params: &EvalParams) -> f64 {
+        let (mut low, mut high) = (0.01, 10.0);
+        for _ in 0..100 {
+            let third = (high - low) / 3.0;
+            self.k = low + third;
+            let left = self.error(params);
+            self.k = high - third;
+            let right = self.error(params);
+            if left < right {
+                high -= third;
+            } else {
+                low += third;
+            }
+        }
+        self.k = (low + high) / 2.0;
+        self.k
+    }
+
+    /// Returns the gradient of the error with respect to the weights.
+    pub fn gradient(&self, params: &EvalParams) -> [f64; TERM_COUNT] {
+        let weights = params.weights();
+        let mut gradient = [0.0; TERM_COUNT];
+        for sample in &self.samples {
+            let prediction = sigmoid(self.k * dot(&weights, &sample.features));
+            let factor = -2.0 * (sample.result - prediction) * prediction * (1.0 - prediction) * self.k;
+            for (gradient, feature) in gradient.iter_mut().zip(sample.features) {
+                *gradient += factor * feature;
+            }
+        }
+
+        let n = self.samples.len().max(1) as f64;
+        gradient.map(|gradient| gradient / n)
+    }
+
+    /// Runs `epochs` epochs of gradient descent and calls `report` with the error after each.
+    ///
+    /// The terms are on very different scales (a count of men versus the sum of their ranks),
+    /// so every weight gets its own step size following the Adam optimizer.
+    pub fn tune(
+        &self,
+        params: &EvalParams,
+        epochs: usize,
+        learning_rate: f64,
+        mut report: impl FnMut(usize, f64),
+    ) -> EvalParams {
+        const BETA1: f64 = 0.9;
+        const BETA2: f64 = 0.999;
+        const EPSILON: f64 = 1e-8;
+
+        let mut weights = params.weights();
+        let mut momentum = [0.0; TERM_COUNT];
+        let mut velocity = [0.0; TERM_COUNT];
+        for epoch in 1..=epochs {
+            let gradient = self.gradient(&EvalParams::from_weights(weights));
+            for term in 0..TERM_COUNT {
+                momentum[term] = BETA1 * momentum[term] + (1.0 - BETA1) * gradient[term];
+                velocity[term] = BETA2 * velocity[term] + (1.0 - BETA2) * gradient[term].powi(2);
+                let momentum = momentum[term] / (1.0 - BETA1.powi(epoch as i32));
+                let velocity = velocity[term] / (1.0 - BETA2.powi(epoch as i32));
+                weights[term] -= learning_rate * momentum / (velocity.sqrt() + EPSILON);
+            }
+            report(epoch, self.error(&EvalParams::from_weights(weights)));
+        }
+        EvalParams::from_weights(weights)
+    }
+}
+
+/// Follows the best capture sequence until the side to move has no capture left.
+/// Returns `None` if the game is over before a quiet position is reached.
+fn quiet_position(evaluator: &dyn Evaluator, backend: &dyn Backend, color: Color) -> Option<Box<dyn Backend>> {
+    let mut position = backend.clone_box();
+    let mut color = color;
+    loop {
+        let plies = position.get_legal_plies(color);
+        if plies.is_empty() {
+            return None;
+        }
+        if plies[0].captures().is_empty() {
+            return Some(position);
+        }
+
+        let mut best = None;
+        for capture in plies {
+            let mut child = position.clone_box();
+            child.ply(capture);
+            let score = -quiescence(evaluator, child.as_ref(), color.opposite());
+            if best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+                best = Some((score, child));
+            }
+        }
+        position = best?.1;
+        color = color.opposite();
+    }
+}
+
+fn parse_result(result: &str) -> Option<f64> {
+    match result {
+        "1-0" => Some(1.0),
+        "0-1" => Some(0.0),
+        "1/2-1/2" => Some(0.5),
+        other => other.parse().ok().filter(|result| (0.0..=1.0).contains(result)),
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn dot(weights: &[f64; TERM_COUNT], features: &[f64; TERM_COUNT]) -> f64 {
+    weights.iter().zip(features).map(|(w, f)| w * f).sum()
+}
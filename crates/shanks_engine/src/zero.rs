@@ -0,0 +1,9 @@
+mod network;
+mod search;
+mod selfplay;
+mod train;
+
+pub use network::{PolicyValueNet, PLANES, POLICY_SIZE};
+pub use search::{ZeroOptions, ZeroPlayer};
+pub use selfplay::{read_samples, self_play, write_samples, SelfPlayConfig, SelfPlayStats, ZeroSample};
+pub use train::{ZeroLoss, ZeroTrainer};
@@ -0,0 +1,327 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use shanks_core::board::{Backend, Color, Piece, Ply, Square};
+
+/// Number of binary input planes: own men, own kings, opponent men and opponent kings on
+/// each of the 32 playable squares.
+pub const PLANES: usize = 128;
+/// Number of policy outputs: the origin square of a ply times the direction of its first step.
+pub const POLICY_SIZE: usize = 128;
+
+const MAGIC: &[u8; 4] = b"SHPV";
+const VERSION: u32 = 1;
+/// Largest layer size a network file may declare, which bounds the memory `read` allocates.
+const MAX_HIDDEN: usize = 1024;
+
+/// A fully connected layer of `f32` weights, stored row by row, one row per output.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Layer {
+    inputs: usize,
+    outputs: usize,
+    weights: Vec<f32>,
+    biases: Vec<f32>,
+}
+
+impl Layer {
+    /// Creates a layer with uniform random weights scaled to the number of inputs.
+    fn random(inputs: usize, outputs: usize, rng: &mut StdRng) -> Self {
+        let bound = (3.0 / inputs as f32).sqrt();
+        Self {
+            inputs,
+            outputs,
+            weights: (0..inputs * outputs).map(|_| rng.gen_range(-bound..bound)).collect(),
+            biases: vec![0.0; outputs],
+        }
+    }
+
+    /// Creates a layer of the same shape with all values zero, used to sum gradients.
+    pub(crate) fn zeros_like(&self) -> Self {
+        Self {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            weights: vec![0.0; self.weights.len()],
+            biases: vec![0.0; self.outputs],
+        }
+    }
+
+    fn forward(&self, input: &[f32]) -> Vec<f32> {
+        self.weights
+            .chunks_exact(self.inputs)
+            .zip(&self.biases)
+            .map(|(row, bias)| bias + row.iter().zip(input).map(|(weight, value)| weight * value).sum::<f32>())
+            .collect()
+    }
+
+    /// Adds the gradients of the weights for one input to `grads` and returns the
+    /// gradient with respect to the input.
+    fn backward(&self, input: &[f32], grad_output: &[f32], grads: &mut Layer) -> Vec<f32> {
+        let mut grad_input = vec![0.0; self.inputs];
+        for (output, &grad) in grad_output.iter().enumerate() {
+            if grad == 0.0 {
+                continue;
+            }
+            grads.biases[output] += grad;
+            let range = output * self.inputs..(output + 1) * self.inputs;
+            let rows = self.weights[range.clone()].iter().zip(&mut grads.weights[range]);
+            for (((weight, grad_weight), value), grad_value) in rows.zip(input).zip(&mut grad_input) {
+                *grad_weight += grad * value;
+                *grad_value += grad * weight;
+            }
+        }
+        grad_input
+    }
+
+    /// Takes a gradient descent step with weight decay on the weights, not the biases.
+    fn step(&mut self, grads: &Layer, rate: f32, decay: f32) {
+        for (weight, grad) in self.weights.iter_mut().zip(&grads.weights) {
+            *weight -= rate * (grad + decay * *weight);
+        }
+        for (bias, grad) in self.biases.iter_mut().zip(&grads.biases) {
+            *bias -= rate * grad;
+        }
+    }
+
+    fn read(reader: &mut impl Read, inputs: usize, outputs: usize) -> io::Result<Self> {
+        Ok(Self { inputs, outputs, weights: read_f32s(reader, inputs * outputs)?, biases: read_f32s(reader, outputs)? })
+    }
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        for value in self.weights.iter().chain(&self.biases) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// The intermediate values of a forward pass, kept for backpropagation.
+pub(crate) struct Activations {
+    planes: Vec<f32>,
+    first: Vec<f32>,
+    second: Vec<f32>,
+    pub(crate) logits: Vec<f32>,
+    pub(crate) value: f32,
+}
+
+/// The gradients of all weights of a [`PolicyValueNet`], summed over a batch.
+pub(crate) struct Gradients {
+    layers: [Layer; 4],
+}
+
+/// A small policy and value network in the style of AlphaZero, cheap enough to train and
+/// run on a CPU.
+///
+/// The network sees the position from the side to move, with the board rotated for black
+/// like the [`Network`](crate::Network) inputs. Two hidden layers of `hidden` ReLU units
+/// feed a policy head and a value head. The policy head has one logit per origin square
+/// and direction of the first step of a ply, which is all it takes to tell apart the
+/// plies of nearly every position; plies that share both get the same logit. The value
+/// head estimates the result for the side to move between -1 for a loss and 1 for a win.
+///
+/// # File format
+///
+/// All values are little-endian, the weights of every layer are stored one output after
+/// the other, followed by its biases.
+///
+/// | Field        | Type                                    |
+/// |--------------|-----------------------------------------|
+/// | magic        | `b"SHPV"`                               |
+/// | version      | `u32`, currently 1                      |
+/// | hidden       | `u32`                                   |
+/// | first layer  | `f32` x `128 * hidden + hidden`         |
+/// | second layer | `f32` x `hidden * hidden + hidden`      |
+/// | policy head  | `f32` x `hidden * 128 + 128`            |
+/// | value head   | `f32` x `hidden + 1`                    |
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyValueNet {
+    hidden: usize,
+    first: Layer,
+    second: Layer,
+    policy: Layer,
+    value: Layer,
+}
+
+impl PolicyValueNet {
+    /// Creates an untrained network with random weights, the starting point of
+    /// reinforcement learning. `hidden` has to be at least 1.
+    pub fn random(hidden: usize, seed: u64) -> Self {
+        assert!(hidden > 0, "a network needs hidden units");
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self {
+            hidden,
+            first: Layer::random(PLANES, hidden, &mut rng),
+            second: Layer::random(hidden, hidden, &mut rng),
+            policy: Layer::random(hidden, POLICY_SIZE, &mut rng),
+            value: Layer::random(hidden, 1, &mut rng),
+        }
+    }
+
+    /// Loads a network from a file in the format described in the type documentation.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(&mut fs::File::open(path)?)
+    }
+
+    pub fn read(reader: &mut impl Read) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a shanks policy/value network file"));
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unsupported version {}", version)));
+        }
+        let hidden = read_u32(reader)? as usize;
+        if !(1..=MAX_HIDDEN).contains(&hidden) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid number of hidden units: {}", hidden),
+            ));
+        }
+
+        Ok(Self {
+            hidden,
+            first: Layer::read(reader, PLANES, hidden)?,
+            second: Layer::read(reader, hidden, hidden)?,
+            policy: Layer::read(reader, hidden, POLICY_SIZE)?,
+            value: Layer::read(reader, hidden, 1)?,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.hidden as u32).to_le_bytes());
+        for layer in [&self.first, &self.second, &self.policy, &self.value] {
+            layer.write(&mut bytes);
+        }
+        fs::write(path, bytes)
+    }
+
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// Evaluates the position for `color`, the side to move. Returns the prior
+    /// probabilities of `plies`, the legal plies of the position, and the expected result
+    /// between -1 and 1.
+    pub fn evaluate(&self, backend: &dyn Backend, color: Color, plies: &[Ply]) -> (Vec<f32>, f32) {
+        let activations = self.forward(backend, color);
+        let logits: Vec<f32> = plies.iter().map(|ply| activations.logits[policy_index(ply, color)]).collect();
+        (softmax(&logits), activations.value)
+    }
+
+    pub(crate) fn forward(&self, backend: &dyn Backend, color: Color) -> Activations {
+        let planes = planes(backend, color);
+        let first = relu(self.first.forward(&planes));
+        let second = relu(self.second.forward(&first));
+        let logits = self.policy.forward(&second);
+        let value = self.value.forward(&second)[0].tanh();
+        Activations { planes, first, second, logits, value }
+    }
+
+    /// Adds the gradients of one position to `grads`, given the gradients of the loss with
+    /// respect to the policy logits and to the value before its `tanh`.
+    pub(crate) fn backward(
+        &self,
+        activations: &Activations,
+        grad_logits: &[f32],
+        grad_value: f32,
+        grads: &mut Gradients,
+    ) {
+        let [first, second, policy, value] = &mut grads.layers;
+        let mut grad_second = self.policy.backward(&activations.second, grad_logits, policy);
+        let grad_from_value = self.value.backward(&activations.second, &[grad_value], value);
+        for ((grad, from_value), &output) in grad_second.iter_mut().zip(grad_from_value).zip(&activations.second) {
+            *grad = if output > 0.0 { *grad + from_value } else { 0.0 };
+        }
+
+        let mut grad_first = self.second.backward(&activations.first, &grad_second, second);
+        for (grad, &output) in grad_first.iter_mut().zip(&activations.first) {
+            if output <= 0.0 {
+                *grad = 0.0;
+            }
+        }
+        self.first.backward(&activations.planes, &grad_first, first);
+    }
+
+    pub(crate) fn gradients(&self) -> Gradients {
+        Gradients { layers: [&self.first, &self.second, &self.policy, &self.value].map(Layer::zeros_like) }
+    }
+
+    /// Takes a gradient descent step with the gradients summed over `count` positions.
+    pub(crate) fn step(&mut self, grads: &Gradients, count: usize, rate: f32, decay: f32) {
+        let rate = rate / count.max(1) as f32;
+        let decay = decay * count.max(1) as f32;
+        for (layer, grads) in
+            [&mut self.first, &mut self.second, &mut self.policy, &mut self.value].into_iter().zip(&grads.layers)
+        {
+            layer.step(grads, rate, decay);
+        }
+    }
+}
+
+/// Returns the policy output of `ply` for `color`, the side to move.
+pub(crate) fn policy_index(ply: &Ply, color: Color) -> usize {
+    let path = ply.path();
+    let (from, next) = (perspective(path[0], color), perspective(path[1], color));
+    let direction = usize::from(next.file() > from.file()) + 2 * usize::from(next.rank() > from.rank());
+    square_index(from) * 4 + direction
+}
+
+/// Returns the probabilities of a softmax over `logits`.
+pub(crate) fn softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = logits.iter().map(|logit| (logit - max).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|exp| exp / sum).collect()
+}
+
+fn planes(backend: &dyn Backend, color: Color) -> Vec<f32> {
+    let mut planes = vec![0.0; PLANES];
+    for square in Square::ALL.into_iter().filter(Square::is_playable) {
+        if let Some(piece) = backend.get_piece(square) {
+            planes[plane(color, square, piece)] = 1.0;
+        }
+    }
+    planes
+}
+
+fn plane(color: Color, square: Square, piece: Piece) -> usize {
+    let owner = if piece.color() == color { 0 } else { 2 };
+    (owner + usize::from(piece.is_king())) * 32 + square_index(perspective(square, color))
+}
+
+fn perspective(square: Square, color: Color) -> Square {
+    match color {
+        Color::White => square,
+        Color::Black => square.flipped(),
+    }
+}
+
+fn square_index(square: Square) -> usize {
+    square.number().map_or(0, |number| number as usize - 1)
+}
+
+fn relu(mut values: Vec<f32>) -> Vec<f32> {
+    for value in &mut values {
+        *value = value.max(0.0);
+    }
+    values
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_f32s(reader: &mut impl Read, count: usize) -> io::Result<Vec<f32>> {
+    let mut bytes = vec![0; count * 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes.chunks_exact(4).map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect())
+}
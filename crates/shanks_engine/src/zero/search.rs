@@ -0,0 +1,280 @@
+use std::{sync::Arc, time::Duration};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use shanks_core::board::{Backend, Board, Color, Ply};
+
+use super::PolicyValueNet;
+use crate::{
+    eval::MAN_VALUE,
+    player::Player,
+    time::{TimeControl, TimeLimits, TimeManager},
+    SearchResult,
+};
+
+/// The clock is read once every this many simulations.
+const TIME_CHECK_INTERVAL: u64 = 16;
+/// Values are kept this far from -1 and 1 when they are turned into scores.
+const MAX_CERTAINTY: f32 = 0.999;
+
+/// Settings of the [`ZeroPlayer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZeroOptions {
+    /// Number of simulations per move, each of which evaluates one new position.
+    pub simulations: u64,
+    /// Weight of the prior against the value of a ply. Larger values follow the
+    /// policy more and explore more.
+    pub c_puct: f32,
+    /// Concentration of the Dirichlet noise added to the root priors.
+    pub dirichlet_alpha: f32,
+    /// Share of the noise in the root priors, zero to play without noise.
+    pub noise: f32,
+    /// Plies are chosen in proportion to their visit counts raised to `1 / temperature`,
+    /// zero always chooses the most visited ply.
+    pub temperature: f32,
+    /// Fixed time per move, which ends the search before all simulations are done.
+    pub movetime: Option<Duration>,
+}
+
+impl Default for ZeroOptions {
+    fn default() -> Self {
+        Self { simulations: 200, c_puct: 1.5, dirichlet_alpha: 0.3, noise: 0.0, temperature: 0.0, movetime: None }
+    }
+}
+
+/// A node of the search tree, reached by `ply` from its parent.
+struct Node {
+    ply: Option<Ply>,
+    parent: usize,
+    children: Vec<usize>,
+    /// The probability of `ply` according to the policy of the parent.
+    prior: f32,
+    visits: u32,
+    /// Sum of the values for the side that played `ply`.
+    value: f32,
+}
+
+impl Node {
+    fn new(ply: Option<Ply>, parent: usize, prior: f32) -> Self {
+        Self { ply, parent, children: Vec::new(), prior, visits: 0, value: 0.0 }
+    }
+}
+
+/// A Monte Carlo tree search guided by a [`PolicyValueNet`], as in AlphaZero.
+///
+/// Every simulation walks down the tree choosing the child with the best PUCT score, the
+/// mean value of a ply plus its prior scaled by how rarely it was visited. The network
+/// evaluates the leaf, its policy gives the priors of the new children and its value is
+/// added to all nodes on the way back up, without playouts. The most visited ply is
+/// played, or a ply is sampled by visit count while the temperature is above zero.
+pub struct ZeroPlayer {
+    network: Arc<PolicyValueNet>,
+    options: ZeroOptions,
+    rng: StdRng,
+    nodes: Vec<Node>,
+}
+
+impl ZeroPlayer {
+    pub fn new(network: Arc<PolicyValueNet>, options: ZeroOptions) -> Self {
+        Self { network, options, rng: StdRng::from_entropy(), nodes: Vec::new() }
+    }
+
+    pub fn options(&self) -> &ZeroOptions {
+        &self.options
+    }
+
+    /// Changes the options, e.g. to stop sampling plies after the opening of a self-play game.
+    pub fn set_options(&mut self, options: ZeroOptions) {
+        self.options = options;
+    }
+
+    pub fn network(&self) -> &Arc<PolicyValueNet> {
+        &self.network
+    }
+
+    /// Seeds the noise and the sampling of plies, which makes the search reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Searches the position until all simulations are done or the time is up.
+    ///
+    /// Returns the result together with the share of the visits of every root ply, the
+    /// policy target of self-play. The score of the result is the value of the chosen ply
+    /// mapped to evaluation units like the score of [`Mcts`](crate::Mcts), `nodes` counts
+    /// the simulations.
+    pub fn search(&mut self, board: &Board, limits: Option<TimeLimits>) -> (SearchResult, Vec<(Ply, f32)>) {
+        let (root, color) = (board.get_backend(), board.to_move());
+        let time = limits.map(TimeManager::new);
+        self.nodes.clear();
+        self.nodes.push(Node::new(None, 0, 1.0));
+        self.expand(0, root, color);
+        if self.options.noise > 0.0 {
+            self.add_noise();
+        }
+
+        let mut simulations = 0;
+        while simulations < self.options.simulations && !self.nodes[0].children.is_empty() {
+            if simulations % TIME_CHECK_INTERVAL == 0 && time.as_ref().is_some_and(TimeManager::past_soft_limit) {
+                break;
+            }
+            self.simulate(root, color);
+            simulations += 1;
+        }
+
+        let visits: u32 = self.nodes[0].children.iter().map(|&child| self.nodes[child].visits).sum();
+        let policy: Vec<(Ply, f32)> = self.nodes[0]
+            .children
+            .iter()
+            .map(|&child| {
+                let node = &self.nodes[child];
+                let ply = node.ply.clone().expect("only the root has no ply");
+                (ply, node.visits as f32 / visits.max(1) as f32)
+            })
+            .collect();
+
+        let chosen = self.choose();
+        let mut pv = Vec::new();
+        let mut node = chosen;
+        while let Some(child) = node {
+            pv.extend(self.nodes[child].ply.clone());
+            node = self.nodes[child].children.iter().copied().max_by_key(|&child| self.nodes[child].visits);
+        }
+        let score = match chosen {
+            Some(child) if self.nodes[child].visits > 0 => {
+                let value = self.nodes[child].value / self.nodes[child].visits as f32;
+                MAN_VALUE * value.clamp(-MAX_CERTAINTY, MAX_CERTAINTY).atanh() as f64
+            }
+            _ => 0.0,
+        };
+        (SearchResult { score, depth: pv.len(), pv, nodes: simulations, tb_hits: 0 }, policy)
+    }
+
+    /// Runs one selection, expansion and backpropagation.
+    fn simulate(&mut self, root: &dyn Backend, mut color: Color) {
+        let mut backend = root.clone_box();
+        let mut node = 0;
+        while !self.nodes[node].children.is_empty() {
+            node = self.select(node);
+            backend.ply(self.nodes[node].ply.clone().expect("only the root has no ply"));
+            color = color.opposite();
+        }
+
+        // The value is for the side to move at the leaf, the node stores it for the side that moved.
+        let mut value = -self.expand(node, backend.as_ref(), color);
+        loop {
+            self.nodes[node].visits += 1;
+            self.nodes[node].value += value;
+            if node == 0 {
+                break;
+            }
+            node = self.nodes[node].parent;
+            value = -value;
+        }
+    }
+
+    /// Adds the legal plies of the position as children of `node` and returns the value
+    /// of the position for `color`, the side to move. A side without plies has lost.
+    fn expand(&mut self, node: usize, backend: &dyn Backend, color: Color) -> f32 {
+        let plies = backend.get_legal_plies(color);
+        if plies.is_empty() {
+            return -1.0;
+        }
+        let (priors, value) = self.network.evaluate(backend, color, &plies);
+        for (ply, prior) in plies.into_iter().zip(priors) {
+            let child = self.nodes.len();
+            self.nodes.push(Node::new(Some(ply), node, prior));
+            self.nodes[node].children.push(child);
+        }
+        value
+    }
+
+    /// Returns the child with the highest PUCT score. Unvisited children count as even.
+    fn select(&self, node: usize) -> usize {
+        let scale = self.options.c_puct * (self.nodes[node].visits.max(1) as f32).sqrt();
+        let score = |child: usize| {
+            let child = &self.nodes[child];
+            let mean = if child.visits == 0 { 0.0 } else { child.value / child.visits as f32 };
+            mean + scale * child.prior / (1 + child.visits) as f32
+        };
+        let children = &self.nodes[node].children;
+        children.iter().copied().max_by(|&a, &b| score(a).total_cmp(&score(b))).expect("the node has children")
+    }
+
+    /// Mixes Dirichlet noise into the priors of the root children, so that self-play
+    /// tries plies the policy does not like yet.
+    fn add_noise(&mut self) {
+        let children = self.nodes[0].children.clone();
+        let noise: Vec<f32> = children.iter().map(|_| gamma(&mut self.rng, self.options.dirichlet_alpha)).collect();
+        let sum: f32 = noise.iter().sum();
+        if sum <= 0.0 {
+            return;
+        }
+        for (child, noise) in children.into_iter().zip(noise) {
+            let prior = &mut self.nodes[child].prior;
+            *prior = (1.0 - self.options.noise) * *prior + self.options.noise * noise / sum;
+        }
+    }
+
+    /// Chooses the root child to play according to the temperature.
+    fn choose(&mut self) -> Option<usize> {
+        let children = &self.nodes[0].children;
+        if self.options.temperature <= 0.0 {
+            return children.iter().copied().max_by_key(|&child| self.nodes[child].visits);
+        }
+        let weights: Vec<f64> = children
+            .iter()
+            .map(|&child| (self.nodes[child].visits as f64).powf(1.0 / self.options.temperature as f64))
+            .collect();
+        let mut target = self.rng.gen::<f64>() * weights.iter().sum::<f64>();
+        for (&child, weight) in children.iter().zip(weights) {
+            if target < weight {
+                return Some(child);
+            }
+            target -= weight;
+        }
+        children.last().copied()
+    }
+}
+
+impl Player for ZeroPlayer {
+    fn name(&self) -> String {
+        format!("zero {} simulations", self.options.simulations)
+    }
+
+    fn think(&mut self, board: &Board) -> SearchResult {
+        self.search(board, self.options.movetime.map(TimeLimits::fixed)).0
+    }
+
+    fn think_with_clock(&mut self, board: &Board, clock: &TimeControl) -> SearchResult {
+        self.search(board, Some(TimeLimits::from_control(clock))).0
+    }
+}
+
+/// Samples the gamma distribution with shape `alpha` and scale 1 by the method of
+/// Marsaglia and Tsang, boosting shapes below 1.
+fn gamma(rng: &mut StdRng, alpha: f32) -> f32 {
+    if alpha < 1.0 {
+        let boost = rng.gen::<f32>().powf(1.0 / alpha);
+        return gamma(rng, alpha + 1.0) * boost;
+    }
+    let d = alpha - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f32 = rng.gen();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Samples the standard normal distribution by the Box-Muller transform.
+fn normal(rng: &mut StdRng) -> f32 {
+    let u: f32 = 1.0 - rng.gen::<f32>();
+    let v: f32 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (std::f32::consts::TAU * v).cos()
+}
@@ -0,0 +1,210 @@
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+use shanks_core::board::{Ballot, Board, Color, Ply};
+
+use super::{PolicyValueNet, ZeroOptions, ZeroPlayer};
+
+/// Settings of a self-play run of the [`ZeroPlayer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelfPlayConfig {
+    /// Number of games to play.
+    pub games: usize,
+    /// Number of games played in parallel.
+    pub threads: usize,
+    /// Search settings of the players. The noise and temperature apply to the first
+    /// `sampling_plies` plies of a game, after which the most visited ply is played
+    /// without noise.
+    pub search: ZeroOptions,
+    pub sampling_plies: usize,
+    /// Start the games from the three-move ballots in turn instead of the initial position.
+    pub ballots: bool,
+    /// Games still running after this many plies are adjudicated as draws.
+    pub max_plies: usize,
+    /// Seed of the searches. Game `i` always plays out the same way with the same network.
+    pub seed: u64,
+}
+
+impl Default for SelfPlayConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            threads: 1,
+            search: ZeroOptions { noise: 0.25, temperature: 1.0, ..Default::default() },
+            sampling_plies: 16,
+            ballots: true,
+            max_plies: 200,
+            seed: 0,
+        }
+    }
+}
+
+/// A position from a self-play game with the training targets of the [`PolicyValueNet`].
+#[derive(Clone)]
+pub struct ZeroSample {
+    pub board: Board,
+    /// The share of the search visits of every legal ply.
+    pub policy: Vec<(Ply, f32)>,
+    /// The result of the game for the side to move: 1 for a win, 0 for a draw, -1 for a loss.
+    pub value: f32,
+}
+
+/// Formats the sample as a `FEN;value;ply:share ...` line, which [`read_samples`] reads back.
+impl fmt::Display for ZeroSample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{};{}", self.board.fen(), self.value)?;
+        for (index, (ply, share)) in self.policy.iter().enumerate() {
+            write!(f, "{}{}:{:.4}", if index == 0 { ';' } else { ' ' }, ply.notation(), share)?;
+        }
+        Ok(())
+    }
+}
+
+/// Totals of a self-play run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SelfPlayStats {
+    pub games: usize,
+    pub white_wins: usize,
+    pub black_wins: usize,
+    pub draws: usize,
+    /// Number of samples handed to the sink.
+    pub samples: usize,
+}
+
+/// Plays `config.games` games of the [`ZeroPlayer`] against itself with `network` and
+/// hands the samples of every finished game to `sink`. Every position of a game with more
+/// than one legal ply becomes a sample, labelled with the visit shares of its search and
+/// the final result.
+pub fn self_play<S>(network: &Arc<PolicyValueNet>, config: &SelfPlayConfig, mut sink: S) -> SelfPlayStats
+where
+    S: FnMut(&[ZeroSample]),
+{
+    let next_game = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..config.threads.max(1) {
+            let sender = sender.clone();
+            let next_game = &next_game;
+            let mut player = ZeroPlayer::new(Arc::clone(network), config.search);
+            scope.spawn(move || loop {
+                let game = next_game.fetch_add(1, Ordering::Relaxed);
+                if game >= config.games {
+                    break;
+                }
+                if sender.send(play_game(&mut player, config, game)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut stats = SelfPlayStats::default();
+        for (winner, samples) in receiver {
+            stats.games += 1;
+            match winner {
+                Some(Color::White) => stats.white_wins += 1,
+                Some(Color::Black) => stats.black_wins += 1,
+                None => stats.draws += 1,
+            }
+            stats.samples += samples.len();
+            sink(&samples);
+        }
+        stats
+    })
+}
+
+/// Plays game number `game` and returns its winner, if any, together with its samples.
+fn play_game(player: &mut ZeroPlayer, config: &SelfPlayConfig, game: usize) -> (Option<Color>, Vec<ZeroSample>) {
+    player.seed(config.seed.wrapping_add(game as u64));
+    let mut board = if config.ballots {
+        let ballots = Ballot::all();
+        ballots[game % ballots.len()].board().expect("the shipped ballots are legal")
+    } else {
+        Board::default()
+    };
+
+    let mut positions = Vec::new();
+    let mut winner = None;
+    for ply_count in 0..config.max_plies {
+        let color = board.to_move();
+        let search = if ply_count < config.sampling_plies {
+            config.search
+        } else {
+            ZeroOptions { noise: 0.0, temperature: 0.0, ..config.search }
+        };
+        player.set_options(search);
+
+        let (result, policy) = player.search(&board, None);
+        let Some(ply) = result.best_ply().cloned() else {
+            winner = Some(color.opposite());
+            break;
+        };
+        if policy.len() > 1 {
+            positions.push((board.clone(), policy));
+        }
+        board.ply(ply);
+    }
+
+    let samples = positions
+        .into_iter()
+        .map(|(board, policy)| {
+            let value = match winner {
+                Some(winner) if winner == board.to_move() => 1.0,
+                Some(_) => -1.0,
+                None => 0.0,
+            };
+            ZeroSample { board, policy, value }
+        })
+        .collect();
+    (winner, samples)
+}
+
+/// Appends the samples to a file, one line per sample.
+pub fn write_samples(path: impl AsRef<Path>, samples: &[ZeroSample]) -> io::Result<()> {
+    let mut file = io::BufWriter::new(fs::OpenOptions::new().create(true).append(true).open(path)?);
+    for sample in samples {
+        writeln!(file, "{}", sample)?;
+    }
+    file.flush()
+}
+
+/// Reads the samples written by [`write_samples`].
+pub fn read_samples(path: impl AsRef<Path>) -> io::Result<Vec<ZeroSample>> {
+    let mut samples = Vec::new();
+    for line in fs::read_to_string(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut fields = line.split(';');
+        let (Some(fen), Some(value), Some(policy), None) = (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid(format!("expected FEN;value;policy: {}", line)));
+        };
+        let board = Board::from_fen(fen).map_err(|err| invalid(format!("{}: {}", err, fen)))?;
+        let value = value.trim().parse().map_err(|_| invalid(format!("invalid value: {}", value)))?;
+
+        let plies = board.get_backend().get_legal_plies(board.to_move());
+        let policy = policy
+            .split_whitespace()
+            .map(|entry| {
+                let (notation, share) = entry.split_once(':')?;
+                let ply = plies.iter().find(|ply| ply.notation() == notation)?;
+                Some((ply.clone(), share.parse().ok()?))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid(format!("invalid policy: {}", policy)))?;
+        samples.push(ZeroSample { board, policy, value });
+    }
+    Ok(samples)
+}
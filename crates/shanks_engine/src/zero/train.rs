@@ -0,0 +1,110 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::{
+    network::{policy_index, softmax, Gradients, POLICY_SIZE},
+    PolicyValueNet, ZeroSample,
+};
+
+/// The mean losses of a [`PolicyValueNet`] over a set of samples.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ZeroLoss {
+    /// Cross-entropy between the visit shares and the policy over the legal plies.
+    pub policy: f32,
+    /// Squared error between the game result and the value.
+    pub value: f32,
+}
+
+impl ZeroLoss {
+    pub fn total(&self) -> f32 {
+        self.policy + self.value
+    }
+}
+
+/// Trains a [`PolicyValueNet`] on self-play samples by minibatch gradient descent.
+///
+/// The loss of a sample is the squared error of the value plus the cross-entropy of the
+/// policy, both as in AlphaZero. The policy is a softmax over the logits of the legal
+/// plies only. Weight decay takes the place of the L2 term of the loss.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZeroTrainer {
+    pub learning_rate: f32,
+    pub weight_decay: f32,
+    pub batch_size: usize,
+}
+
+impl Default for ZeroTrainer {
+    fn default() -> Self {
+        Self { learning_rate: 0.05, weight_decay: 1e-4, batch_size: 32 }
+    }
+}
+
+impl ZeroTrainer {
+    /// Runs `epochs` passes over the samples in an order shuffled with `seed`, calling
+    /// `on_epoch` with the number of the finished epoch and the mean loss during it.
+    pub fn train<F>(
+        &self,
+        network: &mut PolicyValueNet,
+        samples: &[ZeroSample],
+        epochs: usize,
+        seed: u64,
+        mut on_epoch: F,
+    ) where
+        F: FnMut(usize, ZeroLoss),
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut order: Vec<usize> = (0..samples.len()).collect();
+        for epoch in 1..=epochs {
+            order.shuffle(&mut rng);
+            let mut total = ZeroLoss::default();
+            for batch in order.chunks(self.batch_size.max(1)) {
+                let mut grads = network.gradients();
+                for &index in batch {
+                    let loss = accumulate(network, &samples[index], Some(&mut grads));
+                    total.policy += loss.policy;
+                    total.value += loss.value;
+                }
+                network.step(&grads, batch.len(), self.learning_rate, self.weight_decay);
+            }
+            on_epoch(epoch, mean(total, samples.len()));
+        }
+    }
+
+    /// Returns the mean losses of the network on the samples without training it.
+    pub fn loss(network: &PolicyValueNet, samples: &[ZeroSample]) -> ZeroLoss {
+        let mut total = ZeroLoss::default();
+        for sample in samples {
+            let loss = accumulate(network, sample, None);
+            total.policy += loss.policy;
+            total.value += loss.value;
+        }
+        mean(total, samples.len())
+    }
+}
+
+/// Returns the losses of one sample and adds their gradients to `grads`, if given.
+fn accumulate(network: &PolicyValueNet, sample: &ZeroSample, grads: Option<&mut Gradients>) -> ZeroLoss {
+    let color = sample.board.to_move();
+    let activations = network.forward(sample.board.get_backend(), color);
+
+    let indices: Vec<usize> = sample.policy.iter().map(|(ply, _)| policy_index(ply, color)).collect();
+    let logits: Vec<f32> = indices.iter().map(|&index| activations.logits[index]).collect();
+    let probabilities = softmax(&logits);
+    let mut grad_logits = vec![0.0; POLICY_SIZE];
+    let mut policy = 0.0;
+    for ((&index, probability), (_, target)) in indices.iter().zip(&probabilities).zip(&sample.policy) {
+        policy -= target * probability.max(f32::MIN_POSITIVE).ln();
+        grad_logits[index] += probability - target;
+    }
+
+    let error = activations.value - sample.value;
+    if let Some(grads) = grads {
+        let grad_value = 2.0 * error * (1.0 - activations.value * activations.value);
+        network.backward(&activations, &grad_logits, grad_value, grads);
+    }
+    ZeroLoss { policy, value: error * error }
+}
+
+fn mean(total: ZeroLoss, count: usize) -> ZeroLoss {
+    let count = count.max(1) as f32;
+    ZeroLoss { policy: total.policy / count, value: total.value / count }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use shanks_core::board::{parse_pdn, Board, Color};
+use shanks_engine::{Book, Engine};
+
+const GAMES: &str = r#"
+[Event "Test"]
+[Result "1-0"]
+1. 11-15 23-19 2. 8-11 22-17 {main line} 1-0
+
+[Event "Test"]
+[Result "0-1"]
+1. 11-15 (1. 9-13 21-17) 24-20 2. 8-11 0-1
+"#;
+
+#[test]
+fn book_follows_the_games() {
+    let games = parse_pdn(GAMES).unwrap();
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].moves, ["11-15", "23-19", "8-11", "22-17"]);
+    assert_eq!(games[1].result, Some(0.0));
+
+    let mut book = Book::new();
+    for game in &games {
+        book.add_game(game, 16).unwrap();
+    }
+    let start = Board::default();
+    let moves = book.moves(&start);
+    assert_eq!(moves.len(), 1);
+    assert_eq!((moves[0].weight, moves[0].wins, moves[0].losses), (2, 1, 1));
+
+    let path = std::env::temp_dir().join(format!("shanks-book-{}.bin", std::process::id()));
+    book.save(&path).unwrap();
+    let loaded = Book::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.len(), book.len());
+    assert_eq!(loaded.moves(&start), moves);
+
+    let mut engine = Engine::new(Color::White);
+    engine.set_book(Some(Arc::new(loaded)));
+    let result = engine.think(&start);
+    assert_eq!(engine.nodes(), 0);
+    let ply = result.best_ply().unwrap();
+    assert!(moves[0].matches(ply));
+
+    // The two games continue with different replies.
+    let mut board = start;
+    board.ply(ply.clone());
+    assert_eq!(book.moves(&board).len(), 2);
+}
@@ -0,0 +1,72 @@
+use std::fs;
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{
+    generate, DatagenConfig, DatagenStats, Engine, EngineOptions, EvalParams, TrainingPosition, Tuner,
+};
+
+fn engine() -> Engine {
+    Engine::with_options(Color::White, EngineOptions { depth: 3, tt_size: 1 << 12, ..Default::default() })
+}
+
+fn run(config: &DatagenConfig) -> (Vec<Vec<TrainingPosition>>, DatagenStats) {
+    let mut games = Vec::new();
+    let stats = generate(config, engine, |positions| games.push(positions.to_vec()));
+    (games, stats)
+}
+
+#[test]
+fn repeated_positions_are_dropped() {
+    // Without random plies every game plays out the same way.
+    let config = DatagenConfig { games: 3, random_plies: 0, max_plies: 30, ..Default::default() };
+    let (games, stats) = run(&config);
+    assert_eq!(stats.games, 3);
+    assert!(!games[0].is_empty());
+    assert!(games[1..].iter().all(Vec::is_empty));
+    assert_eq!(stats.positions, games[0].len());
+    assert_eq!(stats.duplicates, 2 * games[0].len());
+
+    let mut fens: Vec<&str> = games[0].iter().map(|position| position.fen.as_str()).collect();
+    fens.sort();
+    fens.dedup();
+    assert_eq!(fens.len(), games[0].len());
+}
+
+#[test]
+fn positions_with_captures_are_skipped() {
+    let config = DatagenConfig { games: 4, threads: 2, random_plies: 6, max_plies: 40, seed: 3, ..Default::default() };
+    let (games, stats) = run(&config);
+    assert_eq!(stats.games, 4);
+    assert_eq!(stats.positions, games.iter().map(Vec::len).sum::<usize>());
+    for position in games.iter().flatten() {
+        let board = Board::from_fen(&position.fen).unwrap();
+        let plies = board.get_backend().get_legal_plies(board.to_move());
+        assert!(!plies.is_empty() && plies.iter().all(|ply| ply.captures().is_empty()), "{}", position.fen);
+    }
+}
+
+#[test]
+fn output_round_trips_into_the_tuner() {
+    let config = DatagenConfig { games: 2, random_plies: 4, max_plies: 40, seed: 11, ..Default::default() };
+    let (games, _) = run(&config);
+    for game in &games {
+        assert!(game.windows(2).all(|pair| pair[0].result == pair[1].result));
+        assert!(game.iter().all(|position| [0.0, 0.5, 1.0].contains(&position.result)));
+    }
+
+    let positions: Vec<TrainingPosition> = games.into_iter().flatten().collect();
+    let lines: Vec<String> = positions.iter().map(TrainingPosition::to_string).collect();
+    for (line, position) in lines.iter().zip(&positions) {
+        let fields: Vec<&str> = line.split(';').collect();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(Board::from_fen(fields[0]).unwrap().fen(), position.fen);
+        assert!((fields[1].parse::<f64>().unwrap() - position.score).abs() <= 5e-5);
+        assert_eq!(fields[2].parse::<f64>().unwrap(), position.result);
+    }
+
+    let path = std::env::temp_dir().join(format!("shanks-datagen-{}.txt", std::process::id()));
+    fs::write(&path, lines.join("\n")).unwrap();
+    let tuner = Tuner::load(&path, &EvalParams::default());
+    fs::remove_file(&path).unwrap();
+    assert_eq!(tuner.unwrap().len(), positions.len());
+}
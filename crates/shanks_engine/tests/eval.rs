@@ -0,0 +1,75 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use shanks_core::board::{Backend, Board, Color};
+use shanks_engine::{Engine, EngineOptions, Evaluator, MaterialEvaluator, PositionalEvaluator, KING_VALUE, TERM_COUNT};
+
+const POSITIONS: [&str; 4] = [
+    "W:W21-32:B1-12",
+    "B:W18,21,22,23,24,25,26,27,28,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,15",
+    "W:W5,K14,22,30,32:B1,10,K19,28",
+    "B:WK1,6,K17,29:BK4,K12,16,K32",
+];
+
+/// Counts its calls and always favors white by one unit.
+struct FavorWhite(Arc<AtomicUsize>);
+
+impl Evaluator for FavorWhite {
+    fn evaluate(&self, _backend: &dyn Backend, color: Color) -> f64 {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        match color {
+            Color::White => 1.0,
+            Color::Black => -1.0,
+        }
+    }
+}
+
+fn options() -> EngineOptions {
+    EngineOptions { depth: 4, tt_size: 1 << 12, ..Default::default() }
+}
+
+#[test]
+fn material_is_the_default_evaluation() {
+    let board = Board::from_fen("W:W5,K14,22,30,32:B1,10,K19,28").unwrap();
+    let default = Engine::with_options(Color::White, options()).think(&board);
+    let material = Engine::with_evaluator(Color::White, options(), Box::new(MaterialEvaluator)).think(&board);
+    assert_eq!(default, material);
+}
+
+#[test]
+fn custom_evaluator_drives_the_search() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut engine = Engine::with_evaluator(Color::White, options(), Box::new(FavorWhite(Arc::clone(&calls))));
+    assert_eq!(engine.think(&Board::default()).score, 1.0);
+    assert_eq!(engine.think(&Board::from_fen("B:W21-32:B1-12").unwrap()).score, -1.0);
+    assert!(calls.load(Ordering::Relaxed) > 0);
+
+    engine.set_evaluator(Box::new(MaterialEvaluator));
+    assert_eq!(engine.think(&Board::default()).score, 0.0);
+}
+
+#[test]
+fn breakdown_terms_sum_to_the_evaluation() {
+    let evaluators: [(Box<dyn Evaluator>, usize); 2] =
+        [(Box::new(MaterialEvaluator), 2), (Box::new(PositionalEvaluator::default()), TERM_COUNT)];
+    for (evaluator, terms) in evaluators {
+        for fen in POSITIONS {
+            let board = Board::from_fen(fen).unwrap();
+            let breakdown = evaluator.breakdown(board.get_backend());
+            assert_eq!(breakdown.terms.len(), terms);
+            for color in [Color::White, Color::Black] {
+                let score = evaluator.evaluate(board.get_backend(), color);
+                assert!((breakdown.total(color) - score).abs() < 1e-9, "{} for {}: {}", fen, color, breakdown);
+            }
+        }
+    }
+}
+
+#[test]
+fn kings_are_not_counted_as_men() {
+    // Two men and two kings against two men and one king.
+    let board = Board::from_fen("W:WK5,K14,22,30:B1,10,K19").unwrap();
+    assert_eq!(MaterialEvaluator.evaluate(board.get_backend(), Color::White), KING_VALUE);
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineHandle, EngineOptions, SearchEvent, TimeControl};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+fn engine(depth: usize) -> Engine {
+    Engine::with_options(Color::White, EngineOptions { depth, tt_size: 1 << 14, ..Default::default() })
+}
+
+#[test]
+fn handle_reports_infos_and_the_best_move() {
+    let board = Board::default();
+    let expected = engine(5).think(&board);
+
+    let mut handle = EngineHandle::new(engine(5));
+    handle.go(&board);
+    let mut depths = Vec::new();
+    let (result, ponder) = loop {
+        match handle.events().recv_timeout(TIMEOUT).unwrap() {
+            SearchEvent::Info(info) => depths.push(info.depth),
+            SearchEvent::BestMove { result, ponder, .. } => break (result, ponder),
+        }
+    };
+    assert_eq!(depths, [1, 2, 3, 4, 5]);
+    assert_eq!(result.pv, expected.pv);
+    assert_eq!(ponder.as_ref(), expected.pv.get(1));
+}
+
+#[test]
+fn stop_ends_a_deep_search() {
+    let mut handle = EngineHandle::new(engine(60));
+    handle.go(&Board::default());
+    std::thread::sleep(Duration::from_millis(50));
+    handle.stop();
+    let (result, _) = handle.wait(TIMEOUT).unwrap();
+    assert!(result.depth >= 1 && result.depth < 60);
+    assert!(result.best_ply().is_some());
+}
+
+#[test]
+fn ponder_hit_releases_the_result_and_keeps_the_table() {
+    let board = Board::default();
+    let mut handle = EngineHandle::new(engine(6));
+    handle.ponder(&board);
+    // The ponder search runs to its full depth, but holds back its result.
+    loop {
+        if let SearchEvent::Info(info) = handle.events().recv_timeout(TIMEOUT).unwrap() {
+            if info.depth == 6 {
+                break;
+            }
+        }
+    }
+    assert!(handle.events().recv_timeout(Duration::from_millis(100)).is_err());
+    handle.ponder_hit();
+    let (pondered, _) = handle.wait(TIMEOUT).unwrap();
+
+    // Searching the pondered position again is cheaper thanks to the transposition table.
+    handle.go(&board);
+    let (searched, _) = handle.wait(TIMEOUT).unwrap();
+    assert!(searched.nodes < pondered.nodes);
+    assert_eq!(handle.into_engine().nodes(), searched.nodes);
+}
+
+#[test]
+fn ponder_hit_starts_the_clock() {
+    let clock = TimeControl { remaining: Duration::from_millis(600), increment: Duration::ZERO, moves_to_go: None };
+    let mut handle = EngineHandle::new(engine(60));
+    handle.ponder_with_clock(&Board::default(), &clock);
+    // Pondering is not limited by the clock, which would have run out by now.
+    assert!(handle.wait(Duration::from_millis(300)).is_none());
+
+    let hit = Instant::now();
+    handle.ponder_hit();
+    let (result, _) = handle.wait(TIMEOUT).unwrap();
+    assert!(hit.elapsed() < Duration::from_secs(5));
+    assert!(result.depth >= 1 && result.depth < 60);
+    assert!(result.best_ply().is_some());
+}
+
+#[test]
+fn go_replaces_a_ponder_search() {
+    let mut handle = EngineHandle::new(engine(4));
+    handle.ponder(&Board::default());
+    let board = Board::from_fen("B:W21-32:B1-12").unwrap();
+    handle.go(&board);
+    let (result, _) = handle.wait(TIMEOUT).unwrap();
+    let legal = board.get_backend().get_legal_plies(board.to_move());
+    assert!(legal.contains(result.best_ply().unwrap()));
+    assert!(handle.wait(Duration::from_millis(200)).is_none());
+}
@@ -0,0 +1,32 @@
+use shanks_core::board::{Ballot, Color};
+use shanks_engine::{play_match, Engine, EngineOptions, MatchConfig};
+
+#[test]
+fn every_ballot_is_legal() {
+    let ballots = Ballot::all();
+    assert_eq!(ballots.len(), 302);
+    for (index, ballot) in ballots.iter().enumerate() {
+        assert_eq!(ballot.number(), index + 1);
+        let board = ballot.board().unwrap_or_else(|err| panic!("ballot {}: {}", ballot, err));
+        // Black moved first in standard notation, which is white here, so black is to move.
+        assert_eq!(board.to_move(), Color::Black);
+    }
+    assert_eq!(Ballot::get(1).unwrap().moves(), &["9-13", "21-17", "5-9"]);
+    assert!(Ballot::get(0).is_none());
+}
+
+#[test]
+fn match_plays_both_colors_per_ballot() {
+    let options = EngineOptions { depth: 1, tt_size: 1 << 10, ..Default::default() };
+    let mut first = Engine::with_options(Color::White, options);
+    let mut second = Engine::with_options(Color::White, options);
+    // Starting at the last ballot wraps around to the first.
+    let last = Ballot::all().len();
+    let config = MatchConfig { ballots: 2, first_ballot: last, max_plies: 40, clock: None };
+
+    let mut games = Vec::new();
+    let stats =
+        play_match(&mut first, &mut second, &config, |game| games.push((game.ballot.number(), game.first_color)));
+    assert_eq!(games, [(last, Color::White), (last, Color::Black), (1, Color::White), (1, Color::Black)]);
+    assert_eq!(stats.games(), 4);
+}
@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{
+    play_match, regression_suite, Engine, EngineOptions, MatchConfig, Mcts, MctsOptions, Player, Playout,
+};
+
+fn mcts(iterations: u64, playout: Playout) -> Mcts {
+    let mut mcts = Mcts::new(MctsOptions { iterations, playout, max_playout_plies: 60, ..Default::default() });
+    mcts.seed(0);
+    mcts
+}
+
+#[test]
+fn mcts_plays_legal_plies_reproducibly() {
+    let board = Board::default();
+    let result = mcts(500, Playout::Random).think(&board);
+    assert_eq!(result.nodes, 500);
+    assert!(board.get_backend().get_legal_plies(Color::White).contains(result.best_ply().unwrap()));
+    assert_eq!(mcts(500, Playout::Random).think(&board), result);
+}
+
+#[test]
+fn mcts_finds_the_winning_captures() {
+    let positions = regression_suite();
+    for id in ["regression.001", "regression.003", "regression.006"] {
+        let position = positions.iter().find(|position| position.id == id).unwrap();
+        let result = mcts(3000, Playout::Evaluation).think(&position.board);
+        assert!(position.best.contains(result.best_ply().unwrap()), "{}", id);
+    }
+}
+
+#[test]
+fn movetime_ends_the_search() {
+    let options =
+        MctsOptions { iterations: u64::MAX, movetime: Some(Duration::from_millis(100)), ..Default::default() };
+    let start = Instant::now();
+    let result = Mcts::new(options).think(&Board::default());
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(result.best_ply().is_some());
+}
+
+#[test]
+fn mcts_plays_matches_against_alpha_beta() {
+    let mut mcts = mcts(200, Playout::Evaluation);
+    let mut engine =
+        Engine::with_options(Color::White, EngineOptions { depth: 1, tt_size: 1 << 10, ..Default::default() });
+    let config = MatchConfig { ballots: 1, max_plies: 40, ..Default::default() };
+    let mut games = 0;
+    let stats = play_match(&mut mcts, &mut engine, &config, |_| games += 1);
+    assert_eq!(games, 2);
+    assert_eq!(stats.games(), 2);
+    assert!(mcts.name().starts_with("MCTS"));
+    assert_eq!(Player::name(&engine), "alpha-beta depth 1");
+}
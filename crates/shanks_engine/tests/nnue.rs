@@ -0,0 +1,86 @@
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions, Evaluator, Network};
+
+#[test]
+fn incremental_updates_match_refresh() {
+    let network = Network::random(32, 7);
+    let mut board = Board::from_fen("W:W9,K14,21,22,23,27,30:B3,5,K17,18,19,20").unwrap();
+    let mut accumulator = network.refresh(board.get_backend());
+
+    for i in 0..40 {
+        let Some(ply) = board.get_ply(i * 7 % 5).or_else(|| board.get_ply(0)) else {
+            break;
+        };
+        let mut next = accumulator.clone();
+        network.update(&accumulator, &mut next, board.get_backend(), &ply);
+        board.ply(ply);
+        accumulator = next;
+
+        assert_eq!(accumulator, network.refresh(board.get_backend()), "after {}", board.fen());
+    }
+}
+
+#[test]
+fn network_survives_a_save_and_load() {
+    let network = Network::random(16, 3);
+    let path = std::env::temp_dir().join(format!("shanks-nnue-{}.bin", std::process::id()));
+    network.save(&path).unwrap();
+    let loaded = Network::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(network, loaded);
+}
+
+#[test]
+fn search_with_network_matches_static_evaluation_at_depth_one() {
+    let network = Network::random(16, 11);
+    let board = Board::default();
+
+    let mut engine = Engine::with_options(Color::White, EngineOptions { depth: 1, ..Default::default() });
+    engine.set_network(Some(network.clone()));
+    let result = engine.think(&board);
+
+    let best = result.best_ply().unwrap().clone();
+    let mut child = board.get_backend().clone_box();
+    child.ply(best);
+    // The search rounds evaluations to the resolution of the transposition table.
+    assert!((result.score + network.evaluate(child.as_ref(), Color::Black)).abs() <= 1.0 / 2048.0);
+}
+
+fn network_file(hidden: u32, input: i16, output: i8, bias: i32) -> Vec<u8> {
+    let mut bytes = b"SHNN".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&hidden.to_le_bytes());
+    for _ in 0..129 * hidden {
+        bytes.extend_from_slice(&input.to_le_bytes());
+    }
+    bytes.extend(std::iter::repeat_n(output as u8, 2 * hidden as usize));
+    bytes.extend_from_slice(&bias.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn corrupt_network_files_are_rejected() {
+    let mut bytes = network_file(0, 0, 0, 0);
+    bytes[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = Network::read(&mut bytes.as_slice()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let bytes = network_file(8, 1, 1, 0);
+    assert!(Network::read(&mut &bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn extreme_weights_do_not_overflow() {
+    let network = Network::read(&mut network_file(8, i16::MAX, i8::MAX, i32::MAX).as_slice()).unwrap();
+    let mut board = Board::default();
+    let mut accumulator = network.refresh(board.get_backend());
+    for _ in 0..6 {
+        assert!(network.evaluate(board.get_backend(), board.to_move()).is_finite());
+        let ply = board.get_ply(0).unwrap();
+        let mut next = accumulator.clone();
+        network.update(&accumulator, &mut next, board.get_backend(), &ply);
+        board.ply(ply);
+        accumulator = next;
+        assert_eq!(accumulator, network.refresh(board.get_backend()));
+    }
+}
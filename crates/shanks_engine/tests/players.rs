@@ -0,0 +1,62 @@
+use shanks_core::board::{Board, Color};
+use shanks_engine::{
+    play_match, regression_suite, Evaluator, GreedyPlayer, MatchConfig, MaterialEvaluator, MinimaxPlayer, Player,
+    RandomPlayer,
+};
+
+#[test]
+fn random_player_plays_legal_plies_reproducibly() {
+    let board = Board::default();
+    let mut random = RandomPlayer::new();
+    random.seed(3);
+    let result = random.think(&board);
+    assert!(board.get_backend().get_legal_plies(Color::White).contains(result.best_ply().unwrap()));
+
+    let mut again = RandomPlayer::new();
+    again.seed(3);
+    assert_eq!(again.think(&board), result);
+}
+
+#[test]
+fn greedy_player_maximizes_material() {
+    let mut greedy = GreedyPlayer::new();
+    for position in regression_suite() {
+        let (backend, color) = (position.board.get_backend(), position.board.to_move());
+        let material = |ply: &shanks_core::board::Ply| {
+            let mut child = backend.clone_box();
+            child.ply(ply.clone());
+            MaterialEvaluator.evaluate(child.as_ref(), color)
+        };
+        let best = backend.get_legal_plies(color).iter().map(material).fold(f64::NEG_INFINITY, f64::max);
+        let result = greedy.think(&position.board);
+        assert_eq!(material(result.best_ply().unwrap()), best);
+        assert_eq!(result.score, best);
+    }
+}
+
+#[test]
+fn minimax_player_finds_a_shot() {
+    // Giving up a man on 27 traps the king after the forced recapture.
+    let position = regression_suite().into_iter().find(|position| position.id == "regression.006").unwrap();
+    let result = MinimaxPlayer::new(3).think(&position.board);
+    assert_eq!(result.best_ply().unwrap().notation(), "32-27");
+    assert_eq!(result.pv.len(), 3);
+}
+
+#[test]
+fn baseline_players_play_matches() {
+    let mut random = RandomPlayer::new();
+    random.seed(2);
+    let mut minimax = MinimaxPlayer::new(2);
+    let config = MatchConfig { ballots: 3, max_plies: 200, ..Default::default() };
+    let stats = play_match(&mut minimax, &mut random, &config, |_| {});
+    assert_eq!(stats.games(), 6);
+    assert!(stats.score() > 0.5, "{:?}", stats);
+
+    // Captures are forced, so looking one ply ahead only helps with a choice of captures.
+    let mut greedy = GreedyPlayer::new();
+    greedy.seed(1);
+    let stats = play_match(&mut greedy, &mut random, &MatchConfig { ballots: 1, ..config }, |_| {});
+    assert_eq!(stats.games(), 2);
+    assert_eq!(greedy.name(), "greedy");
+}
@@ -0,0 +1,82 @@
+use shanks_core::board::{Board, Color, Piece, Square};
+use shanks_engine::{EvalParams, Evaluator, PositionalEvaluator, TERM_COUNT};
+
+const POSITIONS: [&str; 6] = [
+    "W:W21-32:B1-12",
+    "B:W18,21,22,23,24,25,26,27,28,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,15",
+    "W:W5,K14,22,30,32:B1,10,K19,28",
+    "B:WK1,6,K17,29:BK4,K12,16,K32",
+    "W:W9,11,27,29,31:B3,20,24,K26",
+    "B:W13,K28,30:B2,K10,17,19",
+];
+
+/// Rotates the board by 180 degrees and swaps the colors of all pieces and the side to move.
+fn flip(board: &Board) -> Board {
+    let mut flipped = Board::from_fen(if board.to_move() == Color::White { "B:W:B" } else { "W:W:B" }).unwrap();
+    for square in Square::ALL {
+        if let Some(piece) = board.get_backend().get_piece(square) {
+            let swapped = Piece::new(piece.color().opposite(), piece.kind());
+            flipped.get_backend_mut().set_piece(square.flipped(), swapped);
+        }
+    }
+    flipped
+}
+
+fn single_term(term: usize) -> EvalParams {
+    let mut weights = [0.0; TERM_COUNT];
+    weights[term] = 1.0;
+    EvalParams::from_weights(weights)
+}
+
+#[test]
+fn every_term_is_symmetric_under_color_flip() {
+    for term in 0..TERM_COUNT {
+        let evaluator = PositionalEvaluator::new(single_term(term));
+        for fen in POSITIONS {
+            let board = Board::from_fen(fen).unwrap();
+            let flipped = flip(&board);
+            for color in [Color::White, Color::Black] {
+                let score = evaluator.evaluate(board.get_backend(), color);
+                let flipped_score = evaluator.evaluate(flipped.get_backend(), color.opposite());
+                assert!(
+                    (score - flipped_score).abs() < 1e-9,
+                    "term {} differs for {} in {}: {} vs {}",
+                    term,
+                    color,
+                    fen,
+                    score,
+                    flipped_score
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn starting_position_is_balanced() {
+    let board = Board::default();
+    let evaluator = PositionalEvaluator::default();
+    assert_eq!(evaluator.evaluate(board.get_backend(), Color::White), 0.0);
+    assert_eq!(evaluator.evaluate(board.get_backend(), Color::Black), 0.0);
+}
+
+#[test]
+fn terms_detect_their_patterns() {
+    let evaluator = |term| PositionalEvaluator::new(single_term(term));
+
+    // White holds the bridge on 30 and 32, black doesn't.
+    let board = Board::from_fen("W:W30,32:B1,2").unwrap();
+    assert_eq!(evaluator(10).evaluate(board.get_backend(), Color::White), 1.0);
+
+    // The white man on 5 is stuck in the dog-hole behind the black man on 1.
+    let board = Board::from_fen("W:W5,30:B1,20").unwrap();
+    assert_eq!(evaluator(9).evaluate(board.get_backend(), Color::White), 1.0);
+
+    // The white man on 14 can't be stopped from crowning, the black man on 20 can.
+    let board = Board::from_fen("W:W14,27:B20").unwrap();
+    assert_eq!(evaluator(6).evaluate(board.get_backend(), Color::White), 1.0);
+
+    // The white king in the corner is boxed in by its own men.
+    let board = Board::from_fen("W:WK29,25,26:BK4").unwrap();
+    assert_eq!(evaluator(8).evaluate(board.get_backend(), Color::White), 1.0);
+}
@@ -0,0 +1,154 @@
+use std::sync::mpsc;
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions, SearchResult};
+
+const POSITIONS: [&str; 4] = [
+    "W:W21-32:B1-12",
+    "B:W18,21,22,23,24,25,26,27,28,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,15",
+    "W:W5,K14,22,30,32:B1,10,K19,28",
+    "W:W17,21,22,23,24,26,27,31,32:B4,7,8,10,11,13,14,15,16",
+];
+
+fn think(fen: &str, options: EngineOptions) -> (f64, u64) {
+    let options = EngineOptions { depth: 6, tt_size: 1 << 16, extensions: false, ..options };
+    let mut engine = Engine::with_options(Color::White, options);
+    let result = engine.think(&Board::from_fen(fen).unwrap());
+    (result.score, result.nodes)
+}
+
+#[test]
+fn pvs_and_aspiration_windows_keep_the_score() {
+    for fen in POSITIONS {
+        let plain = EngineOptions { pvs: false, aspiration: false, lmr: false, ..Default::default() };
+        let (score, _) = think(fen, plain);
+        for (pvs, aspiration) in [(true, false), (false, true), (true, true)] {
+            let (other, _) = think(fen, EngineOptions { pvs, aspiration, ..plain });
+            assert!(
+                (score - other).abs() < 1e-9,
+                "pvs {} aspiration {} in {}: {} vs {}",
+                pvs,
+                aspiration,
+                fen,
+                score,
+                other
+            );
+        }
+    }
+}
+
+#[test]
+fn late_move_reductions_save_nodes() {
+    let without: u64 =
+        POSITIONS.iter().map(|fen| think(fen, EngineOptions { lmr: false, ..Default::default() }).1).sum();
+    let with: u64 = POSITIONS.iter().map(|fen| think(fen, EngineOptions::default()).1).sum();
+    assert!(with < without, "{} nodes with LMR, {} without", with, without);
+}
+
+#[test]
+fn search_reports_every_iteration() {
+    let options = EngineOptions { depth: 5, tt_size: 1 << 12, ..Default::default() };
+    let mut engine = Engine::with_options(Color::White, options);
+    let (sender, receiver) = mpsc::channel();
+    engine.set_info_callback(Some(Box::new(move |info| sender.send(info.clone()).unwrap())));
+
+    let result = engine.think(&Board::default());
+    let infos: Vec<_> = receiver.try_iter().collect();
+    assert_eq!(infos.iter().map(|info| info.depth).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+    assert!(infos[0].branching_factor.is_none());
+    assert!(infos[1..].iter().all(|info| info.branching_factor.is_some_and(|factor| factor > 0.0)));
+    assert!(infos.windows(2).all(|pair| pair[0].stats.nodes < pair[1].stats.nodes));
+
+    let last = infos.last().unwrap();
+    assert_eq!((last.score, &last.pv), (result.score, &result.pv));
+    let stats = engine.stats();
+    assert_eq!(stats.nodes, result.nodes);
+    assert!(stats.seldepth >= 5);
+    assert!(stats.tt_hits <= stats.tt_probes && stats.tt_hits > 0);
+    assert!(stats.first_move_cutoffs <= stats.cutoffs && stats.cutoffs <= stats.expanded);
+    assert!((0.0..=1.0).contains(&stats.cutoff_rate()));
+}
+
+#[test]
+fn multi_pv_ranks_distinct_root_plies() {
+    let options = EngineOptions { depth: 4, tt_size: 1 << 12, multi_pv: 10, ..Default::default() };
+    let mut engine = Engine::with_options(Color::White, options);
+    let board = Board::default();
+    let legal = board.get_backend().get_legal_plies(board.to_move());
+
+    let lines = engine.analyze(&board);
+    assert_eq!(lines.len(), legal.len());
+    assert!(lines.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    let mut first_plies: Vec<_> = lines.iter().map(|line| line.best_ply().unwrap().clone()).collect();
+    first_plies.sort();
+    first_plies.dedup();
+    assert_eq!(first_plies.len(), legal.len());
+
+    // A forced capture leaves a single line.
+    let board = Board::from_fen("W:W22:B18").unwrap();
+    assert_eq!(engine.analyze(&board).len(), 1);
+}
+
+#[test]
+fn multi_pv_lines_do_not_disturb_the_next_search() {
+    for fen in POSITIONS {
+        let board = Board::from_fen(fen).unwrap();
+        let options = EngineOptions { depth: 5, tt_size: 1 << 16, multi_pv: 3, ..Default::default() };
+        let mut engine = Engine::with_options(Color::White, options);
+        let first = engine.analyze(&board);
+        let second = engine.analyze(&board);
+        let summary = |lines: &[SearchResult]| -> Vec<_> {
+            lines.iter().map(|line| (line.best_ply().cloned(), line.score)).collect()
+        };
+        assert_eq!(summary(&first), summary(&second), "{}", fen);
+    }
+}
+
+#[test]
+fn principal_variation_is_legal() {
+    for fen in POSITIONS {
+        let mut board = Board::from_fen(fen).unwrap();
+        let options = EngineOptions { depth: 6, tt_size: 1 << 16, ..Default::default() };
+        let result = Engine::with_options(Color::White, options).think(&board);
+        assert!(!result.pv.is_empty(), "{}", fen);
+        assert_eq!(result.pv_notation().split(' ').count(), result.pv.len());
+        for ply in &result.pv {
+            assert!(
+                board.get_backend().get_legal_plies(board.to_move()).contains(ply),
+                "{} in {}",
+                ply.notation(),
+                fen
+            );
+            board.ply(ply.clone());
+        }
+    }
+}
+
+#[test]
+fn single_thread_search_is_deterministic() {
+    let board = Board::from_fen(POSITIONS[3]).unwrap();
+    let options = EngineOptions { depth: 7, tt_size: 1 << 16, ..Default::default() };
+    let first = Engine::with_options(Color::White, options).think(&board);
+    let second = Engine::with_options(Color::White, options).think(&board);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn lazy_smp_returns_a_legal_pv() {
+    for fen in POSITIONS {
+        let mut board = Board::from_fen(fen).unwrap();
+        let options = EngineOptions { depth: 6, tt_size: 1 << 16, threads: 4, ..Default::default() };
+        let result = Engine::with_options(Color::White, options).think(&board);
+        assert_eq!(result.depth, 6);
+        assert!(!result.pv.is_empty(), "{}", fen);
+        for ply in &result.pv {
+            assert!(
+                board.get_backend().get_legal_plies(board.to_move()).contains(ply),
+                "{} in {}",
+                ply.notation(),
+                fen
+            );
+            board.ply(ply.clone());
+        }
+    }
+}
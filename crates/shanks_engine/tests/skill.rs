@@ -0,0 +1,36 @@
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions, Skill, MAX_SKILL};
+
+#[test]
+fn levels_limit_the_search() {
+    assert_eq!(Skill::new(0).level(), 1);
+    assert_eq!(Skill::new(99).level(), MAX_SKILL);
+    assert!(!Skill::new(MAX_SKILL).is_limited());
+    assert!(Skill::new(1).temperature() > Skill::new(10).temperature());
+    assert_eq!(Skill::new(MAX_SKILL).temperature(), 0.0);
+    for level in 1..MAX_SKILL {
+        let (weaker, stronger) = (Skill::new(level), Skill::new(level + 1));
+        assert!(weaker.elo() <= stronger.elo());
+        assert_eq!(Skill::from_elo(weaker.elo()).elo(), weaker.elo());
+    }
+
+    let options = Skill::new(3).limit(EngineOptions::default());
+    assert!(options.depth < EngineOptions::default().depth);
+    assert!(options.nodes.is_some());
+    assert_eq!(Skill::new(MAX_SKILL).limit(EngineOptions::default()), EngineOptions::default());
+}
+
+#[test]
+fn weak_levels_vary_their_moves() {
+    let board = Board::default();
+    let legal = board.get_backend().get_legal_plies(board.to_move());
+    let mut engine = Engine::with_options(Color::White, EngineOptions { tt_size: 1 << 12, ..Default::default() });
+    engine.set_skill(Some(Skill::new(1)));
+    engine.seed(7);
+
+    let mut plies: Vec<_> = (0..20).map(|_| engine.think(&board).best_ply().unwrap().clone()).collect();
+    assert!(plies.iter().all(|ply| legal.contains(ply)));
+    plies.sort();
+    plies.dedup();
+    assert!(plies.len() > 1);
+}
@@ -0,0 +1,93 @@
+use shanks_core::board::{Board, Color, Ply};
+use shanks_engine::{Outcome, Proof, Solver, SolverOptions, Tablebase};
+
+/// Plays the line and checks that it ends with the loser out of legal plies.
+fn assert_line_ends_the_game(board: &Board, line: &[Ply], winner: Color) {
+    let mut board = board.clone();
+    for ply in line {
+        assert!(
+            board.get_backend().get_legal_plies(board.to_move()).contains(ply),
+            "{} in {}",
+            ply.notation(),
+            board.fen()
+        );
+        board.ply(ply.clone());
+    }
+    assert_eq!(board.to_move(), winner.opposite());
+    assert!(board.get_backend().get_legal_plies(board.to_move()).is_empty(), "{}", board.fen());
+}
+
+#[test]
+fn solver_finds_an_immediate_win() {
+    let board = Board::from_fen("W:W22,30:B18").unwrap();
+    let result = Solver::new(SolverOptions::default()).solve(&board);
+    assert_eq!(result.proof, Proof::Win);
+    assert_eq!(result.line.iter().map(Ply::notation).collect::<Vec<_>>(), ["22x15"]);
+}
+
+#[test]
+fn solver_agrees_with_the_tablebase() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    let mut solver = Solver::new(SolverOptions::default());
+    let mut solved = 0;
+    for slice in tablebase.slices() {
+        let material = slice.material();
+        for index in (0..material.size()).step_by(41) {
+            let Some(pieces) = material.position(index) else {
+                continue;
+            };
+            for color in [Color::White, Color::Black] {
+                let board = Board::from_pieces(color, &pieces);
+                let result = solver.solve(&board);
+                let outcome = tablebase.probe(board.get_backend(), color).unwrap();
+                match outcome {
+                    Outcome::Win(distance) => {
+                        assert_eq!(result.proof, Proof::Win, "{}", board.fen());
+                        assert_eq!(result.line.len(), distance as usize, "{}", board.fen());
+                        assert_line_ends_the_game(&board, &result.line, color);
+                    }
+                    Outcome::Loss(distance) => {
+                        assert_eq!(result.proof, Proof::Loss, "{}", board.fen());
+                        assert_eq!(result.line.len(), distance as usize, "{}", board.fen());
+                        assert_line_ends_the_game(&board, &result.line, color.opposite());
+                    }
+                    Outcome::Draw => assert_eq!(result.proof, Proof::Draw, "{}", board.fen()),
+                }
+                solved += 1;
+            }
+        }
+    }
+    assert!(solved > 100);
+}
+
+#[test]
+fn proof_number_search_proves_wins_and_losses() {
+    // Too little memory to enumerate all reachable positions, so df-pn has to do it.
+    let options = SolverOptions { memory: 1 << 20, nodes: None };
+    for (fen, proof) in [("W:WK18,K19:BK32", Proof::Win), ("W:W23:B14,1", Proof::Loss), ("B:W23:B14,1", Proof::Win)] {
+        let board = Board::from_fen(fen).unwrap();
+        let exhaustive = Solver::new(SolverOptions::default()).solve(&board);
+        let result = Solver::new(options).solve(&board);
+        assert_eq!(exhaustive.proof, proof, "{}", fen);
+        assert_eq!(result.proof, proof, "{}", fen);
+        let winner = if proof == Proof::Win { board.to_move() } else { board.to_move().opposite() };
+        assert_line_ends_the_game(&board, &result.line, winner);
+    }
+}
+
+#[test]
+fn solver_proves_draws() {
+    let board = Board::from_fen("W:WK1:BK32").unwrap();
+    let result = Solver::new(SolverOptions::default()).solve(&board);
+    assert_eq!(result.proof, Proof::Draw);
+    assert!(result.line.is_empty());
+}
+
+#[test]
+fn limits_leave_the_result_unknown() {
+    let mut solver = Solver::new(SolverOptions { memory: 1 << 16, nodes: Some(2000) });
+    let result = solver.solve(&Board::default());
+    assert_eq!(result.proof, Proof::Unknown);
+    assert!(result.line.is_empty());
+    assert!(result.nodes <= 2001);
+}
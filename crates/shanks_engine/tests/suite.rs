@@ -0,0 +1,42 @@
+use shanks_core::board::Color;
+use shanks_engine::{parse_suite, regression_suite, run_suite, Engine, EngineOptions, SuiteError};
+
+#[test]
+fn parse_suite_reads_positions() {
+    let text = "# shots\n\nB:W12,16,18,19,20:B3,4,8,14,K23,25 bm 14-17 3-7; id \"first\"; c0 \"ignored\";\n\
+                W:W22,30:B18,1 bm 22x15;\n";
+    let positions = parse_suite(text).unwrap();
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[0].id, "first");
+    assert_eq!(positions[0].best_notation(), "14-17 3-7");
+    assert_eq!(positions[0].board.to_move(), Color::Black);
+    assert_eq!(positions[1].id, "4");
+    assert_eq!(positions[1].best.len(), 1);
+}
+
+#[test]
+fn parse_suite_reports_errors() {
+    assert!(matches!(parse_suite("W:W22:X18 bm 22-17;").err(), Some(SuiteError::InvalidFen(1, _))));
+    assert_eq!(parse_suite("\nW:W22,30:B18,1 id \"x\";").err(), Some(SuiteError::MissingBestMove(2)));
+    assert_eq!(parse_suite("W:W22,30:B18,1 bm 22-17;").err(), Some(SuiteError::IllegalMove(1, "22-17".into())));
+    assert_eq!(
+        parse_suite("W:W22,30:B18,1 bm 22x15; id first;").err(),
+        Some(SuiteError::InvalidOperation(1, "id first".into()))
+    );
+}
+
+#[test]
+fn engine_solves_the_regression_suite() {
+    let positions = regression_suite();
+    assert!(positions.len() >= 20);
+
+    let options = EngineOptions { depth: 8, tt_size: 1 << 16, ..Default::default() };
+    let mut engine = Engine::with_options(Color::White, options);
+    let mut failed = Vec::new();
+    let solved = run_suite(&mut engine, &positions, |outcome| {
+        if !outcome.solved {
+            failed.push(outcome.position.id.clone());
+        }
+    });
+    assert_eq!(solved, positions.len(), "failed: {:?}", failed);
+}
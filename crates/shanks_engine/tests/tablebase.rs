@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions, Material, Outcome, TableSlice, Tablebase, WIN_SCORE};
+
+#[test]
+fn index_roundtrip() {
+    let material = Material::new(1, 1, 1, 0);
+    let mut positions = 0;
+    for index in 0..material.size() {
+        let Some(pieces) = material.position(index) else {
+            continue;
+        };
+        let board = Board::from_pieces(Color::White, &pieces);
+        assert_eq!(Material::of(board.get_backend()), material);
+        assert_eq!(material.index(board.get_backend()), Some(index));
+        positions += 1;
+    }
+    // Two men of different colors can share neither a square nor a promotion rank.
+    assert_eq!(positions, (28 * 28 - 24) * 30);
+}
+
+#[test]
+fn outcomes_agree_with_their_successors() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    for slice in tablebase.slices() {
+        let material = slice.material();
+        for color in [Color::White, Color::Black] {
+            for index in 0..material.size() {
+                let Some(pieces) = material.position(index) else {
+                    continue;
+                };
+                let board = Board::from_pieces(color, &pieces);
+                let outcome = tablebase.probe(board.get_backend(), color).expect("the position is in the tablebase");
+
+                let successors: Vec<Outcome> = board
+                    .get_backend()
+                    .get_legal_plies(color)
+                    .into_iter()
+                    .map(|ply| {
+                        let mut next = board.get_backend().clone_box();
+                        next.ply(ply);
+                        tablebase.probe(next.as_ref(), color.opposite()).expect("successors are in the tablebase")
+                    })
+                    .collect();
+                let expected = if let Some(loss) = successors
+                    .iter()
+                    .filter_map(|outcome| match outcome {
+                        Outcome::Loss(distance) => Some(*distance),
+                        _ => None,
+                    })
+                    .min()
+                {
+                    Outcome::Win(loss + 1)
+                } else if successors.iter().all(|outcome| matches!(outcome, Outcome::Win(_))) {
+                    Outcome::Loss(successors.iter().filter_map(Outcome::distance).max().map_or(0, |win| win + 1))
+                } else {
+                    Outcome::Draw
+                };
+                assert_eq!(outcome, expected, "{} with {} to move", board.fen(), color);
+            }
+        }
+    }
+}
+
+#[test]
+fn capture_wins_a_king_ending() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    let board = Board::from_fen("W:WK1:BK6").unwrap();
+    assert_eq!(tablebase.probe(board.get_backend(), Color::White), Some(Outcome::Win(1)));
+}
+
+#[test]
+fn engine_probes_the_tablebase() {
+    let tablebase = Arc::new(Tablebase::generate(2, |_| {}));
+    let mut engine = Engine::with_options(Color::White, EngineOptions { depth: 4, ..Default::default() });
+    engine.set_tablebase(Some(tablebase));
+
+    // At the root the engine plays the capture that wins at once.
+    let board = Board::from_fen("W:WK1:BK6").unwrap();
+    let result = engine.think(&board);
+    assert_eq!(result.score, WIN_SCORE - 1.0);
+    assert_eq!(result.best_ply().map(|ply| ply.notation()), Some("1x10".to_string()));
+
+    // With three pieces the search reaches the tablebase after the forced capture.
+    let board = Board::from_fen("W:WK1:BK6,K32").unwrap();
+    let result = engine.think(&board);
+    assert!(result.tb_hits > 0);
+    assert_eq!(result.best_ply().map(|ply| ply.notation()), Some("1x10".to_string()));
+}
+
+#[test]
+fn compressed_slices_match_the_generated_ones() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    let dir = std::env::temp_dir().join(format!("shanks-tablebase-{}", std::process::id()));
+    tablebase.save(&dir, true).unwrap();
+    let compressed = Tablebase::load(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(compressed.len(), tablebase.len());
+    for slice in tablebase.slices() {
+        let loaded = compressed.slice(slice.material()).unwrap();
+        assert!(loaded.is_compressed());
+        assert_eq!(loaded.mismatches(slice), 0);
+        assert_eq!(loaded.counts(), slice.counts());
+        for color in [Color::White, Color::Black] {
+            for index in 0..slice.material().size() {
+                assert_eq!(loaded.get(index, color), slice.get(index, color));
+            }
+        }
+    }
+}
+
+#[test]
+fn compressed_files_with_bad_offsets_are_rejected() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    let slice = tablebase.slice(Material::new(0, 1, 0, 1)).unwrap();
+    let path = std::env::temp_dir().join(format!("shanks-offsets-{}.stc", std::process::id()));
+    slice.save_compressed(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    // The offset of the second block follows the header and the 256 code lengths.
+    let second = 32 + 256 + 8;
+    for offset in [u64::MAX, bytes.len() as u64] {
+        let mut corrupted = bytes.clone();
+        corrupted[second..second + 8].copy_from_slice(&offset.to_le_bytes());
+        std::fs::write(&path, corrupted).unwrap();
+        let error = TableSlice::open_compressed(&path).err().expect("the offsets are invalid");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn compressed_files_with_bad_headers_are_rejected() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    let slice = tablebase.slice(Material::new(0, 1, 0, 1)).unwrap();
+    let path = std::env::temp_dir().join(format!("shanks-codes-{}.stc", std::process::id()));
+    slice.save_compressed(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    let corruptions: [(usize, &[u8]); 2] = [
+        // Code lengths of 1 for all 256 entry values do not form a prefix code.
+        (32, &[1; 256]),
+        (8, &[40, 0, 0, 1]),
+    ];
+    for (at, corruption) in corruptions {
+        let mut corrupted = bytes.clone();
+        corrupted[at..at + corruption.len()].copy_from_slice(corruption);
+        std::fs::write(&path, corrupted).unwrap();
+        let error = TableSlice::open_compressed(&path).err().expect("the header is invalid");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn files_with_illegal_material_are_rejected() {
+    let tablebase = Tablebase::generate(2, |_| {});
+    let slice = tablebase.slice(Material::new(0, 1, 0, 1)).unwrap();
+    let path = std::env::temp_dir().join(format!("shanks-material-{}.stb", std::process::id()));
+    slice.save(&path).unwrap();
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[8..12].copy_from_slice(&[40, 0, 0, 1]);
+    std::fs::write(&path, bytes).unwrap();
+    let error = TableSlice::load(&path).err().expect("the material is illegal");
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    assert!(!Material::new(40, 0, 0, 1).is_legal());
+    assert!(!Material::new(0, 2, 0, 0).is_legal());
+    assert!(!Material::new(8, 5, 1, 0).is_legal());
+    assert!(Material::new(12, 0, 0, 12).is_legal());
+}
@@ -0,0 +1,28 @@
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions};
+
+/// Shots from engine games: the position, the winning ply and the depth at which the
+/// search with extensions finds it. Without extensions these take at least two more plies.
+const SHOTS: [(&str, &str, usize); 6] = [
+    ("B:W12,16,18,19,20:B3,4,8,14,K23,25", "14-17", 4),
+    ("B:W12,13,20,27,28,31,32:B2,3,4,7,8,11,25", "2-6", 6),
+    ("B:W17,19,21,22,23,27,31:B4,8,10,11,13,14,20", "8-12", 6),
+    ("B:W18,20,21,23,24,27,28:B3,4,7,9,11,12,13,14", "7-10", 6),
+    ("W:W17,21,22,23,24,26,27,31,32:B4,7,8,10,11,13,14,15,16", "23-19", 7),
+    ("W:WK10,14,17,20,21,22,27,28,32:B2,3,4,7,8,11,16,K25", "14-9", 7),
+];
+
+fn best_ply(fen: &str, depth: usize, extensions: bool) -> String {
+    let options = EngineOptions { depth, extensions, tt_size: 1 << 18, ..Default::default() };
+    let mut engine = Engine::with_options(Color::White, options);
+    let result = engine.think(&Board::from_fen(fen).unwrap());
+    result.best_ply().unwrap().notation()
+}
+
+#[test]
+fn extensions_find_shots_at_lower_depths() {
+    for (fen, shot, depth) in SHOTS {
+        assert_eq!(best_ply(fen, depth, true), shot, "{}", fen);
+        assert_ne!(best_ply(fen, depth, false), shot, "{}", fen);
+    }
+}
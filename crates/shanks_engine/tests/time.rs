@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{Engine, EngineOptions, TimeControl, TimeLimits};
+
+fn engine(options: EngineOptions) -> Engine {
+    Engine::with_options(Color::White, EngineOptions { tt_size: 1 << 14, ..options })
+}
+
+#[test]
+fn time_limits_budget_the_clock() {
+    let clock = TimeControl { remaining: Duration::from_secs(60), increment: Duration::ZERO, moves_to_go: None };
+    let limits = TimeLimits::from_control(&clock);
+    assert!(limits.soft > Duration::ZERO);
+    assert!(limits.soft < limits.hard);
+    assert!(limits.hard <= clock.remaining / 3);
+
+    let increment = TimeLimits::from_control(&TimeControl { increment: Duration::from_secs(1), ..clock });
+    assert!(increment.soft > limits.soft);
+
+    // With few moves to go, each move may take a larger share of the remaining time.
+    let control = TimeLimits::from_control(&TimeControl { moves_to_go: Some(5), ..clock });
+    assert!(control.soft > limits.soft);
+    assert!(control.hard <= clock.remaining / 3);
+
+    let flagging = TimeLimits::from_control(&TimeControl { remaining: Duration::from_millis(5), ..clock });
+    assert_eq!(flagging.hard, Duration::ZERO);
+}
+
+#[test]
+fn movetime_ends_a_deep_search() {
+    let mut engine =
+        engine(EngineOptions { depth: 60, movetime: Some(Duration::from_millis(100)), ..Default::default() });
+    let start = Instant::now();
+    let result = engine.think(&Board::default());
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(result.depth >= 1 && result.depth < 60);
+    assert!(result.best_ply().is_some());
+}
+
+#[test]
+fn clock_limits_the_search() {
+    let clock = TimeControl { remaining: Duration::from_secs(3), increment: Duration::ZERO, moves_to_go: None };
+    let mut engine = engine(EngineOptions { depth: 60, ..Default::default() });
+    let start = Instant::now();
+    let result = engine.think_with_clock(&Board::default(), &clock);
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(result.depth >= 1 && result.depth < 60);
+}
+
+#[test]
+fn single_reply_is_played_instantly() {
+    // The capture of the black man is forced.
+    let board = Board::from_fen("W:W22,30:B18,1").unwrap();
+    let plies = board.get_backend().get_legal_plies(Color::White);
+    assert_eq!(plies.len(), 1);
+    assert!(!plies[0].captures().is_empty());
+
+    let clock = TimeControl { remaining: Duration::from_secs(60), increment: Duration::ZERO, moves_to_go: None };
+    let mut engine = engine(EngineOptions { depth: 60, ..Default::default() });
+    let result = engine.think_with_clock(&board, &clock);
+    assert_eq!(result.pv, plies);
+    assert_eq!(engine.nodes(), 0);
+}
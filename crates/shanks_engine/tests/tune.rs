@@ -0,0 +1,67 @@
+use shanks_core::board::{Board, Color};
+use shanks_engine::{EvalParams, Evaluator, PositionalEvaluator, Tuner, TERM_COUNT};
+
+/// Quiet positions with the result of a game from white's point of view.
+const POSITIONS: [(&str, f64); 8] = [
+    ("W:W21-32:B1-12", 0.5),
+    ("W:W21-32:B1-11", 1.0),
+    ("B:W22-32:B1-12", 0.0),
+    ("W:W5,K14,22,30,32:B1,10,K19,28", 1.0),
+    ("B:WK1,6,K17,29:BK4,K12,16,K32", 0.5),
+    ("W:W9,11,27,29,31:B3,20,24,K26", 0.5),
+    ("B:W13,K28,30:B2,K10,17,19", 0.0),
+    ("W:W14,27:B20", 1.0),
+];
+
+fn positions() -> Vec<(Board, f64)> {
+    POSITIONS.iter().map(|(fen, result)| (Board::from_fen(fen).unwrap(), *result)).collect()
+}
+
+#[test]
+fn gradient_matches_finite_differences() {
+    let params = EvalParams::default();
+    let mut tuner = Tuner::new(&positions(), &params);
+    assert_eq!(tuner.len(), POSITIONS.len());
+    tuner.fit_k(&params);
+
+    let gradient = tuner.gradient(&params);
+    let step = 1e-6;
+    for term in 0..TERM_COUNT {
+        let mut weights = params.weights();
+        weights[term] += step;
+        let up = tuner.error(&EvalParams::from_weights(weights));
+        weights[term] -= 2.0 * step;
+        let down = tuner.error(&EvalParams::from_weights(weights));
+        let numeric = (up - down) / (2.0 * step);
+        assert!((gradient[term] - numeric).abs() < 1e-6, "term {}: {} vs {}", term, gradient[term], numeric);
+    }
+}
+
+#[test]
+fn fit_k_finds_the_scale_of_the_results() {
+    let params = EvalParams::default();
+    let board = Board::from_fen("W:W21-32:B1-11").unwrap();
+    let score = PositionalEvaluator::new(params).evaluate(board.get_backend(), Color::White);
+    assert!(score > 0.0);
+
+    // Both positions are predicted exactly by a sigmoid with k = 2.
+    let result = 1.0 / (1.0 + (-2.0 * score).exp());
+    let mirrored = Board::from_fen("B:W22-32:B1-12").unwrap();
+    let mut tuner = Tuner::new(&[(board, result), (mirrored, 1.0 - result)], &params);
+    assert!((tuner.fit_k(&params) - 2.0).abs() < 1e-3, "k = {}", tuner.k());
+    assert!(tuner.error(&params) < 1e-9);
+}
+
+#[test]
+fn tuning_reduces_the_error() {
+    let params = EvalParams::default();
+    let mut tuner = Tuner::new(&positions(), &params);
+    tuner.fit_k(&params);
+    let before = tuner.error(&params);
+
+    let mut errors = Vec::new();
+    let tuned = tuner.tune(&params, 20, 0.01, |_, error| errors.push(error));
+    assert_eq!(errors.len(), 20);
+    assert_eq!(tuner.error(&tuned), errors[19]);
+    assert!(errors[19] < before, "{} before, {} after", before, errors[19]);
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use shanks_core::board::{Board, Color};
+use shanks_engine::{
+    play_match, read_samples, self_play, write_samples, MatchConfig, Player, PolicyValueNet, RandomPlayer,
+    SelfPlayConfig, ZeroOptions, ZeroPlayer, ZeroSample, ZeroTrainer,
+};
+
+fn samples(network: &Arc<PolicyValueNet>, games: usize) -> Vec<ZeroSample> {
+    let search = ZeroOptions { simulations: 12, ..SelfPlayConfig::default().search };
+    let config = SelfPlayConfig { games, search, sampling_plies: 8, max_plies: 40, ..Default::default() };
+    let mut samples = Vec::new();
+    let stats = self_play(network, &config, |game| samples.extend_from_slice(game));
+    assert_eq!(stats.games, games);
+    assert_eq!(stats.white_wins + stats.black_wins + stats.draws, games);
+    assert_eq!(stats.samples, samples.len());
+    samples
+}
+
+#[test]
+fn network_survives_a_save_and_load() {
+    let network = PolicyValueNet::random(16, 3);
+    let path = std::env::temp_dir().join(format!("shanks-zero-{}.bin", std::process::id()));
+    network.save(&path).unwrap();
+    let loaded = PolicyValueNet::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(network, loaded);
+}
+
+#[test]
+fn network_files_with_an_invalid_hidden_size_are_rejected() {
+    for hidden in [0, u32::MAX] {
+        let mut bytes = b"SHPV".to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&hidden.to_le_bytes());
+        let err = PolicyValueNet::read(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn network_gives_priors_over_the_legal_plies() {
+    let network = PolicyValueNet::random(16, 5);
+    let board = Board::from_fen("B:W9,K14,21,22,23,27,30:B3,5,K17,18,19,20").unwrap();
+    let plies = board.get_backend().get_legal_plies(Color::Black);
+    let (priors, value) = network.evaluate(board.get_backend(), Color::Black, &plies);
+    assert_eq!(priors.len(), plies.len());
+    assert!((priors.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    assert!(priors.iter().all(|&prior| prior > 0.0));
+    assert!(value > -1.0 && value < 1.0);
+}
+
+#[test]
+fn search_avoids_a_losing_ply() {
+    // 23-18 is captured at once and leaves white without pieces, 23-19 is safe.
+    let board = Board::from_fen("W:W23:B14,1").unwrap();
+    let network = Arc::new(PolicyValueNet::random(16, 7));
+    let mut player = ZeroPlayer::new(network, ZeroOptions { simulations: 100, ..Default::default() });
+    let (result, policy) = player.search(&board, None);
+    assert_eq!(result.best_ply().unwrap().notation(), "23-19");
+    assert_eq!(result.nodes, 100);
+    assert_eq!(policy.len(), 2);
+    assert!((policy.iter().map(|(_, share)| share).sum::<f32>() - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn self_play_samples_survive_a_write_and_read() {
+    let samples = samples(&Arc::new(PolicyValueNet::random(16, 1)), 2);
+    assert!(!samples.is_empty());
+    assert!(samples.iter().all(|sample| [-1.0, 0.0, 1.0].contains(&sample.value) && sample.policy.len() > 1));
+
+    let path = std::env::temp_dir().join(format!("shanks-zero-samples-{}.txt", std::process::id()));
+    write_samples(&path, &samples).unwrap();
+    let loaded = read_samples(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.len(), samples.len());
+    for (loaded, sample) in loaded.iter().zip(&samples) {
+        assert_eq!(loaded.board.fen(), sample.board.fen());
+        assert_eq!(loaded.value, sample.value);
+        for ((loaded_ply, loaded_share), (ply, share)) in loaded.policy.iter().zip(&sample.policy) {
+            assert_eq!(loaded_ply, ply);
+            assert!((loaded_share - share).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn training_reduces_the_loss() {
+    let mut network = PolicyValueNet::random(16, 2);
+    let samples = samples(&Arc::new(network.clone()), 2);
+    let before = ZeroTrainer::loss(&network, &samples);
+
+    let mut losses = Vec::new();
+    ZeroTrainer::default().train(&mut network, &samples, 10, 0, |_, loss| losses.push(loss));
+    assert_eq!(losses.len(), 10);
+    let after = ZeroTrainer::loss(&network, &samples);
+    assert!(after.total() < before.total(), "{:?} -> {:?}", before, after);
+    assert!(after.policy < before.policy);
+}
+
+#[test]
+fn zero_player_plays_matches() {
+    let network = Arc::new(PolicyValueNet::random(16, 4));
+    let mut player = ZeroPlayer::new(network, ZeroOptions { simulations: 8, ..Default::default() });
+    player.seed(0);
+    let mut random = RandomPlayer::new();
+    random.seed(0);
+    let config = MatchConfig { ballots: 1, max_plies: 40, ..Default::default() };
+    let stats = play_match(&mut player, &mut random, &config, |_| {});
+    assert_eq!(stats.games(), 2);
+    assert_eq!(player.name(), "zero 8 simulations");
+}
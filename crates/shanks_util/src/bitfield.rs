@@ -38,6 +38,7 @@ impl BitField {
     ///
     /// # Example
     /// ```
+    /// # use shanks_util::util::BitField;
     /// let bf = BitField::new(0b101);
     /// ```
     pub fn new(value: u64) -> Self {
@@ -152,6 +153,7 @@ impl BitField {
     ///
     /// # Example
     /// ```
+    /// # use shanks_util::util::BitField;
     /// let bf = BitField::new(0b1010);
     /// assert_eq!(bf.positions(), vec![1, 3]);
     /// ```
@@ -50,6 +50,18 @@ fn main() {
     match cli.subcmd {
         SubCommand::Debug(args) => shanks_cli::debug(args),
         SubCommand::Play(args) => shanks_cli::play(args),
-        _ => println!("Not implemented yet"),
+        SubCommand::Bench(args) => shanks_cli::bench(args),
+        SubCommand::Eval(args) => shanks_cli::eval(args),
+        SubCommand::Tune(args) => shanks_cli::tune(args),
+        SubCommand::Datagen(args) => shanks_cli::datagen(args),
+        SubCommand::Tbgen(args) => shanks_cli::tbgen(args),
+        SubCommand::Tbverify(args) => shanks_cli::tbverify(args),
+        SubCommand::Book(args) => shanks_cli::book(args),
+        SubCommand::Match(args) => shanks_cli::matches(args),
+        SubCommand::Analyze(args) => shanks_cli::analyze(args),
+        SubCommand::Calibrate(args) => shanks_cli::calibrate(args),
+        SubCommand::Testsuite(args) => shanks_cli::testsuite(args),
+        SubCommand::Zero(args) => shanks_cli::zero(args),
+        SubCommand::Solve(args) => shanks_cli::solve(args),
     }
 }